@@ -0,0 +1,109 @@
+use core::{convert::Infallible, fmt, str::FromStr};
+
+/// Answer to a puzzle part, whatever type its solution returns.
+///
+/// Text that reads as a number is stored as [`Answer::Number`],
+/// so `"42"` and `42_u64` are the same answer. Text only reads as a number
+/// when the number prints back the same, so that `"007"` stays text.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Answer {
+    Number(u128),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(number) => number.fmt(f),
+            Self::Text(text) => text.fmt(f),
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from(s))
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        number(value).map_or_else(|| Self::Text(value.to_owned()), Self::Number)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        number(&value).map_or(Self::Text(value), Self::Number)
+    }
+}
+
+/// The number `text` is written as, unless it would print differently.
+fn number(text: &str) -> Option<u128> {
+    text.parse()
+        .ok()
+        .filter(|number: &u128| number.to_string() == text)
+}
+
+macro_rules! from_unsigned {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(value: $int) -> Self {
+                    Self::Number(value as u128)
+                }
+            }
+        )*
+    };
+}
+
+from_unsigned!(u8, u16, u32, u64, u128, usize);
+
+impl PartialEq<str> for Answer {
+    fn eq(&self, other: &str) -> bool {
+        match (self, number(other)) {
+            (Self::Number(number), Some(other)) => *number == other,
+            (Self::Text(text), None) => text == other,
+            _ => false,
+        }
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numeric_text_is_a_number() {
+        assert_eq!(Answer::from("1227775554"), Answer::from(1227775554_u64));
+        assert_eq!(Answer::from(String::from("13")), Answer::Number(13));
+        assert_eq!(Answer::from(5_u32), Answer::from(5_usize));
+    }
+
+    #[test]
+    fn other_text_is_text() {
+        let answer = Answer::from("Merry Christmas");
+        assert_eq!(answer, Answer::Text("Merry Christmas".into()));
+        assert_eq!(answer.to_string(), "Merry Christmas");
+        assert_eq!(Answer::from("-3"), Answer::Text("-3".into()));
+        assert_eq!(Answer::from("007"), Answer::Text("007".into()));
+        assert_eq!(Answer::from(String::from("+7")).to_string(), "+7");
+    }
+
+    #[test]
+    fn compares_with_stored_answers() {
+        assert_eq!(Answer::from(40_u64), "40");
+        assert_ne!(Answer::from(40_u64), "040 ");
+        assert_ne!(Answer::from(40_u64), "040");
+        assert_eq!(Answer::from("040"), "040");
+        assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+    }
+}
@@ -0,0 +1,208 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt, io,
+    path::Path,
+};
+
+use miette::{Context, IntoDiagnostic, miette};
+use nom::{
+    IResult, Parser,
+    character::complete::{self, space1},
+    combinator::{all_consuming, rest},
+    sequence::separated_pair,
+};
+
+use crate::Answer;
+
+const HEADER: &str = "# day part answer";
+
+/// Known answers to the real puzzle inputs of one year.
+///
+/// Stored one answer per line as `<day> <part> <answer>`,
+/// blank lines and lines starting with `#` are ignored.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    answers: BTreeMap<(u8, u8), Answer>,
+}
+
+impl Answers {
+    /// Loads the answers at `path`, a missing file has no answers yet.
+    pub fn load(path: impl AsRef<Path>) -> miette::Result<Self> {
+        load(path.as_ref()).map(|entries| Self {
+            answers: entries
+                .into_iter()
+                .map(|(day, part, answer)| ((day, part), answer))
+                .collect(),
+        })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> miette::Result<()> {
+        save(path.as_ref(), self)
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&Answer> {
+        self.answers.get(&(day, part))
+    }
+
+    pub fn insert(&mut self, day: u8, part: u8, answer: Answer) -> Option<Answer> {
+        self.answers.insert((day, part), answer)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u8, u8, &Answer)> {
+        self.answers
+            .iter()
+            .map(|(&(day, part), answer)| (day, part, answer))
+    }
+}
+
+/// Answers the website rejected, so that they are never submitted again.
+///
+/// Stored in the same format as [`Answers`], with any number of answers per part.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WrongAnswers {
+    answers: BTreeSet<(u8, u8, Answer)>,
+}
+
+impl WrongAnswers {
+    /// Loads the wrong answers at `path`, a missing file has none yet.
+    pub fn load(path: impl AsRef<Path>) -> miette::Result<Self> {
+        load(path.as_ref()).map(|entries| Self {
+            answers: entries.into_iter().collect(),
+        })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> miette::Result<()> {
+        save(path.as_ref(), self)
+    }
+
+    pub fn contains(&self, day: u8, part: u8, answer: &Answer) -> bool {
+        self.answers.contains(&(day, part, answer.clone()))
+    }
+
+    pub fn insert(&mut self, day: u8, part: u8, answer: Answer) -> bool {
+        self.answers.insert((day, part, answer))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u8, u8, &Answer)> {
+        self.answers
+            .iter()
+            .map(|(day, part, answer)| (*day, *part, answer))
+    }
+}
+
+impl core::str::FromStr for Answers {
+    type Err = miette::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        entries(s).map(|entries| Self {
+            answers: entries
+                .into_iter()
+                .map(|(day, part, answer)| ((day, part), answer))
+                .collect(),
+        })
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_entries(f, self.iter())
+    }
+}
+
+impl fmt::Display for WrongAnswers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_entries(f, self.iter())
+    }
+}
+
+fn load(path: &Path) -> miette::Result<Vec<(u8, u8, Answer)>> {
+    match std::fs::read_to_string(path) {
+        Ok(text) => entries(&text).with_context(|| format!("parse answers {}", path.display())),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(err)
+            .into_diagnostic()
+            .with_context(|| format!("read answers {}", path.display())),
+    }
+}
+
+fn save(path: &Path, answers: &impl fmt::Display) -> miette::Result<()> {
+    std::fs::write(path, answers.to_string())
+        .into_diagnostic()
+        .with_context(|| format!("write answers {}", path.display()))
+}
+
+fn entries(text: &str) -> miette::Result<Vec<(u8, u8, Answer)>> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|(i, line)| {
+            all_consuming(entry)
+                .parse(line)
+                .map(|(_, entry)| entry)
+                .map_err(|err| miette!("{err}"))
+                .with_context(|| format!("parse answer on line {}", i + 1))
+        })
+        .collect()
+}
+
+fn write_entries<'a>(
+    f: &mut fmt::Formatter<'_>,
+    mut entries: impl Iterator<Item = (u8, u8, &'a Answer)>,
+) -> fmt::Result {
+    writeln!(f, "{HEADER}")?;
+    entries.try_for_each(|(day, part, answer)| writeln!(f, "{day:02} {part} {answer}"))
+}
+
+fn entry(input: &str) -> IResult<&str, (u8, u8, Answer)> {
+    separated_pair(
+        separated_pair(complete::u8, space1, complete::u8),
+        space1,
+        rest.map(|answer: &str| Answer::from(answer.trim_end())),
+    )
+    .map(|((day, part), answer)| (day, part, answer))
+    .parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips() -> miette::Result<()> {
+        let text = "\
+# day part answer
+01 1 1150
+08 2 25272
+12 2 Merry Christmas
+";
+        let answers = text.parse::<Answers>()?;
+        assert_eq!(answers.get(1, 1), Some(&Answer::Number(1150)));
+        assert_eq!(answers.get(1, 2), None);
+        assert_eq!(answers.get(12, 2), Some(&Answer::from("Merry Christmas")));
+        assert_eq!(answers.to_string(), text);
+        Ok(())
+    }
+
+    #[test]
+    fn keeps_every_wrong_answer() -> miette::Result<()> {
+        let dir = std::env::temp_dir().join(format!("advent-answers-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).into_diagnostic()?;
+        let path = dir.join("wrong-answers.txt");
+        let mut wrong = WrongAnswers::load(&path)?;
+        assert!(wrong.insert(8, 1, Answer::Number(100)));
+        assert!(wrong.insert(8, 1, Answer::Number(7)));
+        assert!(!wrong.insert(8, 1, Answer::Number(7)));
+        wrong.save(&path)?;
+        let wrong = WrongAnswers::load(&path)?;
+        assert!(wrong.contains(8, 1, &Answer::Number(100)));
+        assert!(!wrong.contains(8, 2, &Answer::Number(100)));
+        assert_eq!(wrong.to_string(), "# day part answer\n08 1 7\n08 1 100\n");
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        let err = "01 1 3\n1 x 3\n".parse::<Answers>().unwrap_err();
+        assert_eq!(err.to_string(), "parse answer on line 2");
+    }
+}
@@ -0,0 +1,108 @@
+use core::iter;
+
+/// Set of small integers, each a bit in as many words as the largest one needs.
+///
+/// Sets with the same members are equal and hash the same, however they were built.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct BitSet {
+    words: Vec<u64>,
+}
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+impl BitSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set of every integer in `0..len`.
+    pub fn full(len: usize) -> Self {
+        let mut words = vec![u64::MAX; len / WORD_BITS];
+        if !len.is_multiple_of(WORD_BITS) {
+            words.push((1 << (len % WORD_BITS)) - 1);
+        }
+        Self { words }
+    }
+
+    /// Number of members.
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        self.words
+            .get(value / WORD_BITS)
+            .is_some_and(|word| word & bit(value) != 0)
+    }
+
+    /// Adds `value`, returning whether it was new.
+    pub fn insert(&mut self, value: usize) -> bool {
+        let index = value / WORD_BITS;
+        if index >= self.words.len() {
+            self.words.resize(index + 1, 0);
+        }
+        let new = self.words[index] & bit(value) == 0;
+        self.words[index] |= bit(value);
+        new
+    }
+
+    /// Takes out `value`, returning whether it was there.
+    pub fn remove(&mut self, value: usize) -> bool {
+        let Some(word) = self.words.get_mut(value / WORD_BITS) else {
+            return false;
+        };
+        let present = *word & bit(value) != 0;
+        *word &= !bit(value);
+        while self.words.last() == Some(&0) {
+            self.words.pop();
+        }
+        present
+    }
+
+    /// Members in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(index, &word)| {
+            let mut word = word;
+            iter::from_fn(move || {
+                let offset = (word != 0).then(|| word.trailing_zeros() as usize)?;
+                word &= word - 1;
+                Some(index * WORD_BITS + offset)
+            })
+        })
+    }
+}
+
+fn bit(value: usize) -> u64 {
+    1 << (value % WORD_BITS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn holds_any_number_of_members() {
+        let mut set = BitSet::full(70);
+        assert_eq!(set.len(), 70);
+        assert!(set.contains(69) && !set.contains(70));
+        assert!(set.remove(69));
+        assert!(!set.remove(69));
+        assert!(set.insert(200));
+        assert!(!set.insert(200));
+        assert_eq!(set.iter().skip(68).collect::<Vec<_>>(), [68, 200]);
+        assert!(set.remove(200));
+        assert_eq!(set, BitSet::full(69));
+        (0..69).for_each(|value| {
+            set.remove(value);
+        });
+        assert!(set.is_empty());
+        assert_eq!(set, BitSet::new());
+    }
+}
@@ -0,0 +1,106 @@
+/// Partition of the elements `0..len` into disjoint components, merged with
+/// [`union`](Self::union).
+///
+/// Uses path compression and union by size, so merging and looking up
+/// components takes close to constant time.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DisjointSet {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+    components: usize,
+}
+
+impl DisjointSet {
+    /// Every element of `0..len` in a component of its own.
+    pub fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+            components: len,
+        }
+    }
+
+    /// Number of elements, whatever their components.
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// Representative element of the component of `element`.
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        let mut current = element;
+        while current != root {
+            current = core::mem::replace(&mut self.parents[current], root);
+        }
+        root
+    }
+
+    /// Merges the components of `a` and `b`, returning whether they were apart.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (larger, smaller) = if self.sizes[a] < self.sizes[b] {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        self.parents[smaller] = larger;
+        self.sizes[larger] += self.sizes[smaller];
+        self.components -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Number of elements in the component of `element`.
+    pub fn component_size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.sizes[root]
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    /// Sizes of all components, in no particular order.
+    pub fn component_sizes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.parents
+            .iter()
+            .enumerate()
+            .filter(|&(element, &parent)| element == parent)
+            .map(|(root, _)| self.sizes[root])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_components() {
+        let mut set = DisjointSet::new(6);
+        assert_eq!(set.component_count(), 6);
+        assert!(set.union(0, 1));
+        assert!(set.union(2, 3));
+        assert!(set.union(1, 3));
+        assert!(!set.union(0, 2));
+        assert!(set.connected(0, 3));
+        assert!(!set.connected(0, 4));
+        assert_eq!(set.component_size(2), 4);
+        assert_eq!(set.component_count(), 3);
+        let mut sizes = set.component_sizes().collect::<Vec<_>>();
+        sizes.sort_unstable();
+        assert_eq!(sizes, [1, 1, 4]);
+    }
+}
@@ -0,0 +1,58 @@
+use std::{fs, io, path::Path};
+
+use miette::{Context, IntoDiagnostic, miette};
+
+use crate::{
+    Answer,
+    input::{example_answer_path, example_path},
+};
+
+/// Example input of a puzzle part together with the answer the puzzle gives for it.
+///
+/// Kept in the `examples` directory of the day crate as `part-<n>.txt`
+/// and `part-<n>.answer`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub answer: Answer,
+}
+
+impl Example {
+    pub fn load(day_dir: impl AsRef<Path>, part: u8) -> miette::Result<Self> {
+        let day_dir = day_dir.as_ref();
+        let input = read(&example_path(day_dir, part))?;
+        let answer = read(&example_answer_path(day_dir, part))?;
+        Ok(Self {
+            input,
+            answer: Answer::from(answer.trim()),
+        })
+    }
+
+    pub fn save(&self, day_dir: impl AsRef<Path>, part: u8) -> miette::Result<()> {
+        let day_dir = day_dir.as_ref();
+        let input_path = example_path(day_dir, part);
+        let answer_path = example_answer_path(day_dir, part);
+        if let Some(dir) = input_path.parent() {
+            fs::create_dir_all(dir)
+                .into_diagnostic()
+                .with_context(|| format!("create {}", dir.display()))?;
+        }
+        fs::write(&input_path, &self.input)
+            .into_diagnostic()
+            .with_context(|| format!("write {}", input_path.display()))?;
+        fs::write(&answer_path, format!("{}\n", self.answer))
+            .into_diagnostic()
+            .with_context(|| format!("write {}", answer_path.display()))
+    }
+}
+
+fn read(path: &Path) -> miette::Result<String> {
+    fs::read_to_string(path).map_err(|err| match err.kind() {
+        io::ErrorKind::NotFound => miette!(
+            help = "extract it from the puzzle page with `aoc examples`",
+            "example {} does not exist",
+            path.display()
+        ),
+        _ => miette!("{err}").wrap_err(format!("read example {}", path.display())),
+    })
+}
@@ -0,0 +1,286 @@
+use core::fmt;
+
+/// Integer usable as a coordinate, with distances measured exactly in `u128`.
+pub trait Coordinate: Copy + Ord + fmt::Debug {
+    fn distance(self, other: Self) -> u128;
+}
+
+macro_rules! coordinate {
+    ($($int:ty),*) => {
+        $(
+            impl Coordinate for $int {
+                fn distance(self, other: Self) -> u128 {
+                    self.abs_diff(other) as u128
+                }
+            }
+        )*
+    };
+}
+
+coordinate!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Coordinate> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    /// Square of the euclidean distance, exact as long as it fits in a `u128`.
+    pub fn squared_distance(&self, other: &Self) -> u128 {
+        [self.x.distance(other.x), self.y.distance(other.y)]
+            .into_iter()
+            .map(|d| d * d)
+            .sum()
+    }
+
+    pub fn manhattan_distance(&self, other: &Self) -> u128 {
+        self.x.distance(other.x) + self.y.distance(other.y)
+    }
+}
+
+impl<T: Coordinate> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Square of the euclidean distance, exact as long as it fits in a `u128`.
+    pub fn squared_distance(&self, other: &Self) -> u128 {
+        [
+            self.x.distance(other.x),
+            self.y.distance(other.y),
+            self.z.distance(other.z),
+        ]
+        .into_iter()
+        .map(|d| d * d)
+        .sum()
+    }
+
+    pub fn manhattan_distance(&self, other: &Self) -> u128 {
+        self.x.distance(other.x) + self.y.distance(other.y) + self.z.distance(other.z)
+    }
+}
+
+/// Horizontal or vertical line segment between two points, both included.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct Segment<T> {
+    pub from: Point2<T>,
+    pub to: Point2<T>,
+}
+
+impl<T: Coordinate> Segment<T> {
+    /// Segment from `from` to `to`, unless it is diagonal.
+    pub fn new(from: Point2<T>, to: Point2<T>) -> Option<Self> {
+        (from.x == to.x || from.y == to.y).then_some(Self { from, to })
+    }
+
+    pub fn is_vertical(&self) -> bool {
+        self.from.x == self.to.x && self.from.y != self.to.y
+    }
+
+    pub fn is_horizontal(&self) -> bool {
+        self.from.y == self.to.y && self.from.x != self.to.x
+    }
+
+    /// Number of points on the segment, counting both ends.
+    pub fn point_count(&self) -> u128 {
+        self.from.manhattan_distance(&self.to) + 1
+    }
+
+    pub fn min(&self) -> Point2<T> {
+        Point2::new(self.from.x.min(self.to.x), self.from.y.min(self.to.y))
+    }
+
+    pub fn max(&self) -> Point2<T> {
+        Point2::new(self.from.x.max(self.to.x), self.from.y.max(self.to.y))
+    }
+
+    /// Whether the inside of the segments, without their ends, meet.
+    ///
+    /// Perpendicular segments cross where both their insides meet, parallel ones
+    /// when they lie on the same line and an end of one is inside the other.
+    pub fn crosses(&self, other: &Self) -> bool {
+        let (min, max) = (self.min(), self.max());
+        let (other_min, other_max) = (other.min(), other.max());
+        let inside = |value: T, min: T, max: T| min < value && value < max;
+        match (self.is_vertical(), other.is_vertical()) {
+            (true, true) => {
+                min.x == other_min.x
+                    && (inside(other_min.y, min.y, max.y)
+                        || inside(other_max.y, min.y, max.y)
+                        || inside(min.y, other_min.y, other_max.y)
+                        || inside(max.y, other_min.y, other_max.y))
+            }
+            (false, false) => {
+                min.y == other_min.y
+                    && (inside(other_min.x, min.x, max.x)
+                        || inside(other_max.x, min.x, max.x)
+                        || inside(min.x, other_min.x, other_max.x)
+                        || inside(max.x, other_min.x, other_max.x))
+            }
+            (true, false) => {
+                inside(other.from.y, min.y, max.y) && inside(self.from.x, other_min.x, other_max.x)
+            }
+            (false, true) => {
+                inside(other.from.x, min.x, max.x) && inside(self.from.y, other_min.y, other_max.y)
+            }
+        }
+    }
+
+    pub fn contains_point(&self, point: Point2<T>) -> bool {
+        let (min, max) = (self.min(), self.max());
+        (min.x..=max.x).contains(&point.x) && (min.y..=max.y).contains(&point.y)
+    }
+
+    pub fn contains(&self, other: &Self) -> bool {
+        self.contains_point(other.from) && self.contains_point(other.to)
+    }
+}
+
+/// Axis aligned rectangle spanned by two opposite corners, covering the points
+/// between them with its edges included.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct Rectangle<T> {
+    a: Point2<T>,
+    b: Point2<T>,
+}
+
+impl<T: Coordinate> Rectangle<T> {
+    pub fn new(a: Point2<T>, b: Point2<T>) -> Self {
+        Self { a, b }
+    }
+
+    pub fn min(&self) -> Point2<T> {
+        Point2::new(self.a.x.min(self.b.x), self.a.y.min(self.b.y))
+    }
+
+    pub fn max(&self) -> Point2<T> {
+        Point2::new(self.a.x.max(self.b.x), self.a.y.max(self.b.y))
+    }
+
+    pub fn width(&self) -> u128 {
+        self.a.x.distance(self.b.x) + 1
+    }
+
+    pub fn height(&self) -> u128 {
+        self.a.y.distance(self.b.y) + 1
+    }
+
+    /// Number of points covered, edges included.
+    pub fn area(&self) -> u128 {
+        self.width() * self.height()
+    }
+
+    pub fn contains(&self, point: Point2<T>) -> bool {
+        let (min, max) = (self.min(), self.max());
+        (min.x..=max.x).contains(&point.x) && (min.y..=max.y).contains(&point.y)
+    }
+
+    /// The corners going around the rectangle, starting at the first one it was made from.
+    pub fn corners(&self) -> [Point2<T>; 4] {
+        let (a, b) = (self.a, self.b);
+        [a, Point2::new(b.x, a.y), b, Point2::new(a.x, b.y)]
+    }
+
+    /// The sides of the rectangle, those through the first corner running to it and
+    /// those through the opposite corner running from it.
+    ///
+    /// A rectangle as thin as a line has that one side and a point has none.
+    pub fn edges(&self) -> impl Iterator<Item = Segment<T>> {
+        let [a, ba, b, ab] = self.corners();
+        let mut edges = [(ab, a), (ba, a), (b, ab), (b, ba)]
+            .into_iter()
+            .filter(|(from, to)| from != to)
+            .map(|(from, to)| Segment { from, to })
+            .collect::<Vec<_>>();
+        edges.dedup();
+        edges.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measures_distances_exactly() {
+        let a = Point3::new(162_u32, 817, 812);
+        let b = Point3::new(425, 690, 689);
+        assert_eq!(a.squared_distance(&b), 263 * 263 + 127 * 127 + 123 * 123);
+        assert_eq!(a.manhattan_distance(&b), 263 + 127 + 123);
+        let far = Point2::new(u64::MAX, 0);
+        let origin = Point2::new(0, 0);
+        assert_eq!(
+            far.squared_distance(&origin),
+            u128::from(u64::MAX) * u128::from(u64::MAX)
+        );
+        assert_eq!(
+            Point2::new(-3_i64, 4).manhattan_distance(&Point2::new(3, -4)),
+            14
+        );
+    }
+
+    fn segment(from: (i32, i32), to: (i32, i32)) -> Segment<i32> {
+        Segment::new(Point2::new(from.0, from.1), Point2::new(to.0, to.1)).unwrap()
+    }
+
+    #[test]
+    fn segments_cross_inside() {
+        assert!(Segment::new(Point2::new(0, 0), Point2::new(1, 1)).is_none());
+        let vertical = segment((2, 0), (2, 4));
+        assert!(vertical.is_vertical() && !vertical.is_horizontal());
+        assert!(vertical.crosses(&segment((0, 2), (4, 2))));
+        assert!(!vertical.crosses(&segment((2, 2), (4, 2))));
+        assert!(!vertical.crosses(&segment((0, 4), (4, 4))));
+        assert!(vertical.crosses(&segment((2, 3), (2, 8))));
+        assert!(!vertical.crosses(&segment((2, 4), (2, 8))));
+        assert!(!vertical.crosses(&segment((3, 1), (3, 3))));
+    }
+
+    #[test]
+    fn segments_contain_points_and_segments() {
+        let horizontal = segment((1, 5), (7, 5));
+        assert_eq!(horizontal.point_count(), 7);
+        assert!(horizontal.contains_point(Point2::new(7, 5)));
+        assert!(!horizontal.contains_point(Point2::new(8, 5)));
+        assert!(horizontal.contains(&segment((3, 5), (2, 5))));
+        assert!(!horizontal.contains(&segment((3, 5), (9, 5))));
+    }
+
+    #[test]
+    fn rectangles_have_area_and_edges() {
+        let rectangle = Rectangle::new(Point2::new(9_u64, 5), Point2::new(2, 3));
+        assert_eq!(rectangle.area(), 24);
+        assert!(rectangle.contains(Point2::new(2, 5)));
+        assert!(!rectangle.contains(Point2::new(1, 5)));
+        assert_eq!(rectangle.corners()[0], Point2::new(9, 5));
+        assert_eq!(rectangle.min(), Point2::new(2, 3));
+        let edges = rectangle.edges().collect::<Vec<_>>();
+        assert_eq!(edges.len(), 4);
+        assert!(
+            edges
+                .iter()
+                .all(|edge| edge.is_vertical() != edge.is_horizontal())
+        );
+        let line = Rectangle::new(Point2::new(2_u64, 3), Point2::new(2, 7));
+        assert_eq!(line.area(), 5);
+        assert_eq!(line.edges().count(), 1);
+        assert_eq!(
+            Rectangle::new(Point2::new(1_u64, 1), Point2::new(1, 1))
+                .edges()
+                .count(),
+            0
+        );
+    }
+}
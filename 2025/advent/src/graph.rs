@@ -0,0 +1,878 @@
+use core::{
+    cmp::Reverse,
+    fmt::{self, Write},
+    iter,
+    ops::Range,
+};
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+use crate::bitset::BitSet;
+
+/// Index of a node in a [`Graph`], handed out in the order the names are first seen.
+pub type NodeId = usize;
+
+/// Number of paths, added up with overflow checked so that counts can be as
+/// wide as the caller needs.
+pub trait PathCount: Clone {
+    fn zero() -> Self;
+
+    fn one() -> Self;
+
+    fn is_zero(&self) -> bool;
+
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+}
+
+macro_rules! path_count {
+    ($($int:ty),*) => {
+        $(
+            impl PathCount for $int {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn one() -> Self {
+                    1
+                }
+
+                fn is_zero(&self) -> bool {
+                    *self == 0
+                }
+
+                fn checked_add(&self, other: &Self) -> Option<Self> {
+                    <$int>::checked_add(*self, *other)
+                }
+            }
+        )*
+    };
+}
+
+path_count!(u32, u64, u128, usize);
+
+/// Why paths could not be counted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CountError {
+    /// The graph has a cycle, so paths could go around it any number of times.
+    Cycle,
+    /// More paths reach the node than the count can hold.
+    Overflow(NodeId),
+}
+
+impl fmt::Display for CountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cycle => write!(f, "graph has a cycle"),
+            Self::Overflow(node) => write!(f, "too many paths reach node {node} to count"),
+        }
+    }
+}
+
+impl std::error::Error for CountError {}
+
+/// Directed graph between named nodes.
+///
+/// Names are interned to [`NodeId`]s and the edges are stored as one list of
+/// targets ordered by source, so the successors of a node are a slice of it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Graph {
+    names: Vec<Box<str>>,
+    ids: HashMap<Box<str>, NodeId>,
+    declared: Vec<bool>,
+    offsets: Vec<usize>,
+    targets: Vec<NodeId>,
+}
+
+impl Graph {
+    /// Graph with an edge from each source to every one of its targets.
+    ///
+    /// A source listed more than once keeps the targets of all its lists.
+    pub fn new<S, T>(adjacency: impl IntoIterator<Item = (S, T)>) -> Self
+    where
+        S: AsRef<str>,
+        T: IntoIterator<Item = S>,
+    {
+        let mut graph = Self::default();
+        let mut successors = Vec::<Vec<NodeId>>::new();
+        for (source, targets) in adjacency {
+            let source = graph.intern(source.as_ref());
+            let targets = targets
+                .into_iter()
+                .map(|target| graph.intern(target.as_ref()))
+                .collect::<Vec<_>>();
+            successors.resize_with(graph.len(), Vec::new);
+            successors[source].extend(targets);
+            graph.declared[source] = true;
+        }
+        successors.resize_with(graph.len(), Vec::new);
+        graph.offsets = Vec::with_capacity(graph.len() + 1);
+        graph.offsets.push(0);
+        for targets in successors {
+            graph.targets.extend(targets);
+            graph.offsets.push(graph.targets.len());
+        }
+        graph
+    }
+
+    /// Number of nodes.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn nodes(&self) -> Range<NodeId> {
+        0..self.len()
+    }
+
+    pub fn id(&self, name: &str) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, node: NodeId) -> &str {
+        &self.names[node]
+    }
+
+    /// Whether `node` was listed as a source, rather than only as the target of others.
+    pub fn is_declared(&self, node: NodeId) -> bool {
+        self.declared[node]
+    }
+
+    /// Nodes only ever listed as targets.
+    pub fn undeclared(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.nodes().filter(|&node| !self.is_declared(node))
+    }
+
+    pub fn successors(&self, node: NodeId) -> &[NodeId] {
+        &self.targets[self.offsets[node]..self.offsets[node + 1]]
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = (NodeId, NodeId)> + '_ {
+        self.nodes().flat_map(move |source| {
+            self.successors(source)
+                .iter()
+                .map(move |&target| (source, target))
+        })
+    }
+
+    /// Nodes ordered so that every edge goes forward, unless the graph has a cycle.
+    pub fn topological_order(&self) -> Option<Vec<NodeId>> {
+        let mut in_degrees = vec![0_usize; self.len()];
+        self.targets
+            .iter()
+            .for_each(|&target| in_degrees[target] += 1);
+        let mut ready = self
+            .nodes()
+            .filter(|&node| in_degrees[node] == 0)
+            .collect::<VecDeque<_>>();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(node) = ready.pop_front() {
+            order.push(node);
+            for &target in self.successors(node) {
+                in_degrees[target] -= 1;
+                if in_degrees[target] == 0 {
+                    ready.push_back(target);
+                }
+            }
+        }
+        (order.len() == self.len()).then_some(order)
+    }
+
+    /// Nodes going around a cycle, each followed by an edge to the next and the
+    /// last by an edge back to the first, if the graph has one.
+    pub fn find_cycle(&self) -> Option<Vec<NodeId>> {
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        enum Visit {
+            New,
+            Open,
+            Done,
+        }
+        let mut visits = vec![Visit::New; self.len()];
+        for root in self.nodes() {
+            if visits[root] != Visit::New {
+                continue;
+            }
+            visits[root] = Visit::Open;
+            let mut path = vec![(root, 0)];
+            while let Some((node, next)) = path.last_mut() {
+                let Some(&target) = self.successors(*node).get(*next) else {
+                    visits[*node] = Visit::Done;
+                    path.pop();
+                    continue;
+                };
+                *next += 1;
+                match visits[target] {
+                    Visit::New => {
+                        visits[target] = Visit::Open;
+                        path.push((target, 0));
+                    }
+                    Visit::Open => {
+                        let start = path.iter().position(|&(node, _)| node == target)?;
+                        return Some(path[start..].iter().map(|&(node, _)| node).collect());
+                    }
+                    Visit::Done => {}
+                }
+            }
+        }
+        None
+    }
+
+    /// Which nodes can be reached from `from`, itself included, indexed by [`NodeId`].
+    pub fn reachable(&self, from: NodeId) -> Vec<bool> {
+        let mut reached = vec![false; self.len()];
+        reached[from] = true;
+        let mut stack = vec![from];
+        while let Some(node) = stack.pop() {
+            for &target in self.successors(node) {
+                if !reached[target] {
+                    reached[target] = true;
+                    stack.push(target);
+                }
+            }
+        }
+        reached
+    }
+
+    /// Which nodes can reach `to`, itself included, indexed by [`NodeId`].
+    pub fn reaching(&self, to: NodeId) -> Vec<bool> {
+        let predecessors = self.predecessors();
+        let mut reached = vec![false; self.len()];
+        reached[to] = true;
+        let mut stack = vec![to];
+        while let Some(node) = stack.pop() {
+            for &source in &predecessors[node] {
+                if !reached[source] {
+                    reached[source] = true;
+                    stack.push(source);
+                }
+            }
+        }
+        reached
+    }
+
+    /// Fewest edges from each node to `to`, none for the nodes that cannot reach it.
+    pub fn distances_to(&self, to: NodeId) -> Vec<Option<usize>> {
+        let predecessors = self.predecessors();
+        let mut distances = vec![None; self.len()];
+        distances[to] = Some(0);
+        let mut queue = VecDeque::from([to]);
+        while let Some(node) = queue.pop_front() {
+            let distance = distances[node].map(|distance| distance + 1);
+            for &source in &predecessors[node] {
+                if distances[source].is_none() {
+                    distances[source] = distance;
+                    queue.push_back(source);
+                }
+            }
+        }
+        distances
+    }
+
+    /// Paths from `from` to `to` that visit no node twice, found depth first as
+    /// the iterator is advanced.
+    pub fn paths(&self, from: NodeId, to: NodeId) -> impl Iterator<Item = Vec<NodeId>> + '_ {
+        self.paths_through(from, &[], to)
+    }
+
+    /// Paths from `from` to `to` that visit no node twice and pass every one of
+    /// `waypoints`, in any order, found depth first as the iterator is advanced.
+    ///
+    /// A path is only extended to nodes from which it can still pass the
+    /// waypoints it has not and then reach `to`.
+    pub fn paths_through<'a>(
+        &'a self,
+        from: NodeId,
+        waypoints: &[NodeId],
+        to: NodeId,
+    ) -> impl Iterator<Item = Vec<NodeId>> + use<'a> {
+        let route = Route::new(self, waypoints, to);
+        let mut remaining = BitSet::full(waypoints.len());
+        let mut on_path = vec![false; self.len()];
+        let mut path = Vec::<(NodeId, usize)>::new();
+        let mut entering = route.length(from, &remaining).map(|_| from);
+        iter::from_fn(move || {
+            loop {
+                if let Some(node) = entering.take() {
+                    route.pass(node, &mut remaining);
+                    if node == to {
+                        let found = remaining.is_empty().then(|| {
+                            let mut found = path.iter().map(|&(node, _)| node).collect::<Vec<_>>();
+                            found.push(to);
+                            found
+                        });
+                        route.leave(node, &mut remaining);
+                        match found {
+                            Some(found) => return Some(found),
+                            None => continue,
+                        }
+                    }
+                    on_path[node] = true;
+                    path.push((node, 0));
+                }
+                let (node, next) = path.last_mut()?;
+                match self.successors(*node).get(*next) {
+                    Some(&target) => {
+                        *next += 1;
+                        if !on_path[target] && route.length(target, &remaining).is_some() {
+                            entering = Some(target);
+                        }
+                    }
+                    None => {
+                        on_path[*node] = false;
+                        route.leave(*node, &mut remaining);
+                        path.pop();
+                    }
+                }
+            }
+        })
+    }
+
+    /// Paths from `from` to `to` that visit no node twice, fewest edges first.
+    pub fn shortest_paths(
+        &self,
+        from: NodeId,
+        to: NodeId,
+    ) -> impl Iterator<Item = Vec<NodeId>> + '_ {
+        self.shortest_paths_through(from, &[], to)
+    }
+
+    /// Paths from `from` to `to` that visit no node twice and pass every one of
+    /// `waypoints`, in any order, fewest edges first.
+    ///
+    /// Partial paths are expanded best first by their length plus the fewest
+    /// edges they still need to pass the remaining waypoints and reach `to`, so
+    /// only those that can still be among the next shortest are extended. They
+    /// are kept as steps back to the partial path they extend.
+    pub fn shortest_paths_through<'a>(
+        &'a self,
+        from: NodeId,
+        waypoints: &[NodeId],
+        to: NodeId,
+    ) -> impl Iterator<Item = Vec<NodeId>> + use<'a> {
+        let route = Route::new(self, waypoints, to);
+        let mut steps = Vec::<(NodeId, Option<usize>)>::new();
+        let mut frontier = BinaryHeap::new();
+        if let Some(length) = route.length(from, &BitSet::full(waypoints.len())) {
+            steps.push((from, None));
+            frontier.push(Reverse((length + 1, Reverse(0))));
+        }
+        iter::from_fn(move || {
+            while let Some(Reverse((_, Reverse(step)))) = frontier.pop() {
+                let path = trace(&steps, step);
+                let mut remaining = BitSet::full(route.waypoints.len());
+                path.iter()
+                    .for_each(|&node| route.pass(node, &mut remaining));
+                let node = steps[step].0;
+                if node == to {
+                    if remaining.is_empty() {
+                        return Some(path);
+                    }
+                    continue;
+                }
+                for &target in self.successors(node) {
+                    if let Some(length) = route.length(target, &remaining)
+                        && !path.contains(&target)
+                    {
+                        steps.push((target, Some(step)));
+                        let length = path.len() + 1 + length;
+                        frontier.push(Reverse((length, Reverse(steps.len() - 1))));
+                    }
+                }
+            }
+            None
+        })
+    }
+
+    /// The graph in Graphviz DOT, with the attributes in `highlights` added to their nodes.
+    pub fn to_dot(&self, highlights: &[(NodeId, &str)]) -> String {
+        let quote = |node: NodeId| format!("\"{}\"", self.name(node).replace('"', "\\\""));
+        let mut dot = String::from("digraph {\n");
+        for node in self.nodes() {
+            let attributes = highlights
+                .iter()
+                .filter(|&&(highlighted, _)| highlighted == node)
+                .map(|&(_, attributes)| attributes)
+                .collect::<Vec<_>>();
+            if attributes.is_empty() {
+                writeln!(dot, "    {};", quote(node))
+            } else {
+                writeln!(dot, "    {} [{}];", quote(node), attributes.join(", "))
+            }
+            .expect("writing to a string to succeed");
+        }
+        for (source, target) in self.edges() {
+            writeln!(dot, "    {} -> {};", quote(source), quote(target))
+                .expect("writing to a string to succeed");
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Number of distinct paths from `from` to `to`, as long as the graph has no
+    /// cycle and the number fits in `C`.
+    ///
+    /// Only nodes that lead to `to` are counted, so that branches which never
+    /// get there cannot overflow.
+    pub fn count_paths<C: PathCount>(&self, from: NodeId, to: NodeId) -> Result<C, CountError> {
+        let order = self.topological_order().ok_or(CountError::Cycle)?;
+        let leads_to = self.reaching(to);
+        let mut paths = vec![C::zero(); self.len()];
+        if leads_to[from] {
+            paths[from] = C::one();
+        }
+        for node in order {
+            if paths[node].is_zero() || node == to {
+                continue;
+            }
+            for &target in self
+                .successors(node)
+                .iter()
+                .filter(|&&target| leads_to[target])
+            {
+                paths[target] = paths[target]
+                    .checked_add(&paths[node])
+                    .ok_or(CountError::Overflow(target))?;
+            }
+        }
+        Ok(paths[to].clone())
+    }
+
+    /// Which nodes every path from `root` to them passes through.
+    pub fn dominator_tree(&self, root: NodeId) -> DominatorTree {
+        let predecessors = self.predecessors();
+        DominatorTree::new(
+            self.len(),
+            root,
+            |node| self.successors(node),
+            |node| &predecessors[node],
+        )
+    }
+
+    /// Which nodes every path from them to `exit` passes through.
+    pub fn post_dominator_tree(&self, exit: NodeId) -> DominatorTree {
+        let predecessors = self.predecessors();
+        DominatorTree::new(
+            self.len(),
+            exit,
+            |node| &predecessors[node],
+            |node| self.successors(node),
+        )
+    }
+
+    /// Nodes that every path from `from` to `to` passes through, in the order
+    /// they are passed and both ends included, unless `to` cannot be reached.
+    pub fn mandatory_nodes(&self, from: NodeId, to: NodeId) -> Option<Vec<NodeId>> {
+        let mut nodes = self.dominator_tree(from).dominators(to).collect::<Vec<_>>();
+        nodes.reverse();
+        (!nodes.is_empty()).then_some(nodes)
+    }
+
+    /// Nodes apart from `from` and `to` whose removal leaves no path between them,
+    /// unless there is none to begin with.
+    pub fn articulation_nodes(&self, from: NodeId, to: NodeId) -> Option<Vec<NodeId>> {
+        let nodes = self.mandatory_nodes(from, to)?;
+        Some(
+            nodes
+                .into_iter()
+                .filter(|&node| node != from && node != to)
+                .collect(),
+        )
+    }
+
+    fn predecessors(&self) -> Vec<Vec<NodeId>> {
+        let mut predecessors = vec![Vec::new(); self.len()];
+        self.edges()
+            .for_each(|(source, target)| predecessors[target].push(source));
+        predecessors
+    }
+
+    fn intern(&mut self, name: &str) -> NodeId {
+        if let Some(id) = self.id(name) {
+            return id;
+        }
+        let id = self.names.len();
+        self.names.push(Box::from(name));
+        self.declared.push(false);
+        self.ids.insert(Box::from(name), id);
+        id
+    }
+}
+
+/// Distances to the waypoints of a path and to its end, to tell how much longer
+/// a partial path has to grow to pass the waypoints it has not, if it can at all.
+struct Route {
+    to: Vec<Option<usize>>,
+    waypoints: Vec<(NodeId, Vec<Option<usize>>)>,
+}
+
+impl Route {
+    fn new(graph: &Graph, waypoints: &[NodeId], to: NodeId) -> Self {
+        Self {
+            to: graph.distances_to(to),
+            waypoints: waypoints
+                .iter()
+                .map(|&waypoint| (waypoint, graph.distances_to(waypoint)))
+                .collect(),
+        }
+    }
+
+    /// Fewest edges that lead on from `node` through the waypoints in `remaining`
+    /// to the end, none if they cannot all be reached.
+    fn length(&self, node: NodeId, remaining: &BitSet) -> Option<usize> {
+        remaining.iter().try_fold(self.to[node]?, |length, index| {
+            let (waypoint, distances) = &self.waypoints[index];
+            Some(length.max(distances[node]? + self.to[*waypoint]?))
+        })
+    }
+
+    /// Marks the waypoints at `node` as passed.
+    fn pass(&self, node: NodeId, remaining: &mut BitSet) {
+        self.at(node).for_each(|index| {
+            remaining.remove(index);
+        });
+    }
+
+    /// Marks the waypoints at `node` as not passed again.
+    fn leave(&self, node: NodeId, remaining: &mut BitSet) {
+        self.at(node).for_each(|index| {
+            remaining.insert(index);
+        });
+    }
+
+    /// Indices of the waypoints at `node`.
+    fn at(&self, node: NodeId) -> impl Iterator<Item = usize> + '_ {
+        self.waypoints
+            .iter()
+            .enumerate()
+            .filter(move |&(_, &(waypoint, _))| waypoint == node)
+            .map(|(index, _)| index)
+    }
+}
+
+/// Nodes of the path that ends with `step`, each step being a node and the
+/// step before it.
+fn trace(steps: &[(NodeId, Option<usize>)], step: usize) -> Vec<NodeId> {
+    let mut path = iter::successors(Some(step), |&step| steps[step].1)
+        .map(|step| steps[step].0)
+        .collect::<Vec<_>>();
+    path.reverse();
+    path
+}
+
+/// Immediate dominator of every node reachable from a root, where a node
+/// dominates another when every path from the root to that one passes it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DominatorTree {
+    root: NodeId,
+    immediate_dominators: Vec<Option<NodeId>>,
+}
+
+impl DominatorTree {
+    /// Uses the iterative algorithm of Cooper, Harvey and Kennedy, refining the
+    /// dominators in reverse postorder until they settle.
+    fn new<'a>(
+        len: usize,
+        root: NodeId,
+        successors: impl Fn(NodeId) -> &'a [NodeId],
+        predecessors: impl Fn(NodeId) -> &'a [NodeId],
+    ) -> Self {
+        let mut postorder = Vec::with_capacity(len);
+        let mut postorder_index = vec![None; len];
+        let mut visited = vec![false; len];
+        visited[root] = true;
+        let mut stack = vec![(root, 0)];
+        while let Some((node, next)) = stack.last_mut() {
+            match successors(*node).get(*next) {
+                Some(&target) => {
+                    *next += 1;
+                    if !visited[target] {
+                        visited[target] = true;
+                        stack.push((target, 0));
+                    }
+                }
+                None => {
+                    postorder_index[*node] = Some(postorder.len());
+                    postorder.push(*node);
+                    stack.pop();
+                }
+            }
+        }
+
+        let mut immediate_dominators = vec![None; len];
+        immediate_dominators[root] = Some(root);
+        let intersect = |dominators: &[Option<NodeId>], mut a: NodeId, mut b: NodeId| {
+            while a != b {
+                while postorder_index[a] < postorder_index[b] {
+                    a = dominators[a].expect("processed nodes to have a dominator");
+                }
+                while postorder_index[b] < postorder_index[a] {
+                    b = dominators[b].expect("processed nodes to have a dominator");
+                }
+            }
+            a
+        };
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &node in postorder.iter().rev().filter(|&&node| node != root) {
+                let dominator = predecessors(node)
+                    .iter()
+                    .copied()
+                    .filter(|&predecessor| immediate_dominators[predecessor].is_some())
+                    .reduce(|a, b| intersect(&immediate_dominators, a, b));
+                if dominator.is_some() && immediate_dominators[node] != dominator {
+                    immediate_dominators[node] = dominator;
+                    changed = true;
+                }
+            }
+        }
+        immediate_dominators[root] = None;
+        Self {
+            root,
+            immediate_dominators,
+        }
+    }
+
+    pub fn root(&self) -> NodeId {
+        self.root
+    }
+
+    /// Closest dominator of `node` other than itself, none for the root and
+    /// nodes that cannot be reached.
+    pub fn immediate_dominator(&self, node: NodeId) -> Option<NodeId> {
+        self.immediate_dominators[node]
+    }
+
+    pub fn is_reachable(&self, node: NodeId) -> bool {
+        node == self.root || self.immediate_dominators[node].is_some()
+    }
+
+    /// Dominators of `node` from itself up to the root, none if it cannot be reached.
+    pub fn dominators(&self, node: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        let first = self.is_reachable(node).then_some(node);
+        iter::successors(first, |&node| self.immediate_dominators[node])
+    }
+
+    pub fn dominates(&self, dominator: NodeId, node: NodeId) -> bool {
+        self.dominators(node).any(|other| other == dominator)
+    }
+}
+
+/// Adjacency of the nodes of layers `0` to `layers`, two per layer and each
+/// leading to both nodes of the next, so that the number of paths doubles with
+/// every layer. Meant for tests of counts that outgrow their integer type.
+pub fn layered(layers: usize) -> Vec<(String, Vec<String>)> {
+    (0..layers)
+        .flat_map(|n| {
+            let targets = layer(n + 1).to_vec();
+            layer(n).map(|node| (node, targets.clone()))
+        })
+        .collect()
+}
+
+/// Names of the two nodes of layer `n` of [`layered`], `x` and `y` followed by
+/// the digits of `n` as the letters `a` to `j`, such as `xbc` for layer 12.
+pub fn layer(n: usize) -> [String; 2] {
+    let letters = n
+        .to_string()
+        .bytes()
+        .map(|digit| char::from(digit - b'0' + b'a'))
+        .collect::<String>();
+    [format!("x{letters}"), format!("y{letters}")]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diamond() -> Graph {
+        Graph::new([
+            ("a", vec!["b", "c"]),
+            ("b", vec!["d"]),
+            ("c", vec!["d", "e"]),
+            ("e", vec!["d"]),
+        ])
+    }
+
+    #[test]
+    fn interns_names() {
+        let graph = diamond();
+        assert_eq!(graph.len(), 5);
+        let [a, b, c, d] = ["a", "b", "c", "d"].map(|name| graph.id(name).unwrap());
+        assert_eq!(graph.name(d), "d");
+        assert_eq!(graph.successors(a), [b, c]);
+        assert!(graph.successors(d).is_empty());
+        assert_eq!(graph.id("z"), None);
+        assert_eq!(graph.edges().count(), 6);
+        assert!(graph.is_declared(c));
+        assert_eq!(graph.undeclared().collect::<Vec<_>>(), [d]);
+    }
+
+    #[test]
+    fn orders_and_counts_paths() {
+        let graph = diamond();
+        let [a, c, d] = ["a", "c", "d"].map(|name| graph.id(name).unwrap());
+        let order = graph.topological_order().unwrap();
+        assert!(
+            graph
+                .edges()
+                .all(|(from, to)| order.iter().position(|&n| n == from)
+                    < order.iter().position(|&n| n == to))
+        );
+        assert_eq!(graph.count_paths::<u32>(a, d), Ok(3));
+        assert_eq!(graph.count_paths::<u32>(c, d), Ok(2));
+        assert_eq!(graph.count_paths::<u32>(d, a), Ok(0));
+        assert_eq!(graph.reachable(c).iter().filter(|&&r| r).count(), 3);
+    }
+
+    #[test]
+    fn detects_overflowing_counts() {
+        let graph = Graph::new(layered(70));
+        let [from, to, overflowing] = [0, 70, 65].map(|n| graph.id(&layer(n)[0]).unwrap());
+        assert_eq!(graph.count_paths::<u128>(from, to), Ok(1 << 69));
+        assert_eq!(
+            graph.count_paths::<u64>(from, to),
+            Err(CountError::Overflow(overflowing))
+        );
+    }
+
+    #[test]
+    fn ignores_branches_that_miss_the_end() {
+        let [first, _] = layer(0);
+        let exit = [("s".to_owned(), vec![first, "t".to_owned()])];
+        let graph = Graph::new(exit.into_iter().chain(layered(70)));
+        let [s, t] = ["s", "t"].map(|name| graph.id(name).unwrap());
+        assert_eq!(graph.count_paths::<u32>(s, t), Ok(1));
+        assert_eq!(graph.count_paths::<u32>(t, s), Ok(0));
+    }
+
+    #[test]
+    fn enumerates_paths() {
+        let graph = diamond();
+        let [a, b, c, d, e] = ["a", "b", "c", "d", "e"].map(|name| graph.id(name).unwrap());
+        let paths = graph.paths(a, d).collect::<Vec<_>>();
+        assert_eq!(paths, [vec![a, b, d], vec![a, c, d], vec![a, c, e, d]]);
+        assert_eq!(graph.paths(a, a).collect::<Vec<_>>(), [vec![a]]);
+        assert_eq!(graph.paths(d, a).count(), 0);
+        let shortest = graph.shortest_paths(a, d).map(|path| path.len());
+        assert_eq!(shortest.collect::<Vec<_>>(), [3, 3, 4]);
+        let cyclic = Graph::new([("a", ["b"]), ("b", ["a"])]);
+        assert_eq!(cyclic.paths(0, 1).collect::<Vec<_>>(), [vec![0, 1]]);
+        assert_eq!(cyclic.shortest_paths(1, 0).count(), 1);
+        assert_eq!(
+            graph.distances_to(d),
+            [Some(2), Some(1), Some(1), Some(0), Some(1)]
+        );
+    }
+
+    #[test]
+    fn finds_shortest_paths_among_many() {
+        let [[first, _], [last, _]] = [0, 60].map(layer);
+        let shortcut = [(first.clone(), vec![last.clone()])];
+        let graph = Graph::new(layered(60).into_iter().chain(shortcut));
+        let [from, to] = [first, last].map(|name| graph.id(&name).unwrap());
+        let shortest = graph
+            .shortest_paths(from, to)
+            .take(3)
+            .map(|path| path.len());
+        assert_eq!(shortest.collect::<Vec<_>>(), [2, 61, 61]);
+    }
+
+    #[test]
+    fn finds_paths_through_waypoints() {
+        let graph = diamond();
+        let [a, b, c, d, e] = ["a", "b", "c", "d", "e"].map(|name| graph.id(name).unwrap());
+        let through =
+            |waypoints: &[NodeId]| graph.paths_through(a, waypoints, d).collect::<Vec<_>>();
+        assert_eq!(through(&[e]), [vec![a, c, e, d]]);
+        assert_eq!(through(&[c]), [vec![a, c, d], vec![a, c, e, d]]);
+        assert_eq!(through(&[d, a]), graph.paths(a, d).collect::<Vec<_>>());
+        assert!(through(&[b, e]).is_empty());
+        let shortest = graph.shortest_paths_through(a, &[c], d).collect::<Vec<_>>();
+        assert_eq!(shortest, [vec![a, c, d], vec![a, c, e, d]]);
+        assert_eq!(graph.shortest_paths_through(a, &[b, e], d).count(), 0);
+    }
+
+    #[test]
+    fn skips_branches_that_miss_waypoints() {
+        let [[first, _], [last, _]] = [0, 60].map(layer);
+        let detour = [
+            ("s".to_owned(), vec![first, "w".to_owned()]),
+            ("w".to_owned(), vec![last.clone()]),
+        ];
+        let graph = Graph::new(detour.into_iter().chain(layered(60)));
+        let [s, w, to] = ["s", "w", &last].map(|name| graph.id(name).unwrap());
+        assert_eq!(
+            graph.paths_through(s, &[w], to).collect::<Vec<_>>(),
+            [vec![s, w, to]]
+        );
+        let shortest = graph.shortest_paths_through(s, &[w], to);
+        assert_eq!(shortest.collect::<Vec<_>>(), [vec![s, w, to]]);
+    }
+
+    #[test]
+    fn exports_dot() {
+        let graph = Graph::new([("a", ["b \"quoted\""])]);
+        assert_eq!(
+            graph.to_dot(&[(0, "shape=box"), (0, "color=red")]),
+            r#"digraph {
+    "a" [shape=box, color=red];
+    "b \"quoted\"";
+    "a" -> "b \"quoted\"";
+}
+"#
+        );
+    }
+
+    #[test]
+    fn finds_dominators() {
+        let graph = Graph::new([
+            ("s", vec!["a"]),
+            ("a", vec!["b", "c"]),
+            ("b", vec!["d"]),
+            ("c", vec!["d"]),
+            ("d", vec!["e", "a"]),
+            ("e", vec!["t"]),
+            ("x", vec!["e"]),
+        ]);
+        let [s, a, d, e, t, x] = ["s", "a", "d", "e", "t", "x"].map(|name| graph.id(name).unwrap());
+        let dominators = graph.dominator_tree(s);
+        assert_eq!(dominators.root(), s);
+        assert_eq!(dominators.immediate_dominator(d), Some(a));
+        assert_eq!(dominators.immediate_dominator(s), None);
+        assert!(!dominators.is_reachable(x));
+        assert!(dominators.dominates(a, t) && !dominators.dominates(t, a));
+        let post_dominators = graph.post_dominator_tree(t);
+        assert_eq!(post_dominators.immediate_dominator(a), Some(d));
+        assert_eq!(post_dominators.immediate_dominator(x), Some(e));
+        assert_eq!(graph.mandatory_nodes(s, t), Some(vec![s, a, d, e, t]));
+        assert_eq!(graph.articulation_nodes(s, t), Some(vec![a, d, e]));
+        assert_eq!(graph.articulation_nodes(x, t), Some(vec![e]));
+        assert_eq!(graph.mandatory_nodes(t, s), None);
+    }
+
+    #[test]
+    fn cycles_have_no_order() {
+        let graph = Graph::new([("a", ["b"]), ("b", ["a"])]);
+        assert_eq!(graph.topological_order(), None);
+        assert_eq!(graph.count_paths::<u64>(0, 1), Err(CountError::Cycle));
+    }
+
+    #[test]
+    fn finds_cycles() {
+        assert_eq!(diamond().find_cycle(), None);
+        let graph = Graph::new([
+            ("a", vec!["b"]),
+            ("b", vec!["c", "e"]),
+            ("c", vec!["d"]),
+            ("d", vec!["b"]),
+        ]);
+        let cycle = graph.find_cycle().unwrap();
+        let names = cycle
+            .iter()
+            .map(|&node| graph.name(node))
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["b", "c", "d"]);
+    }
+}
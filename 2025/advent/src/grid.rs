@@ -0,0 +1,306 @@
+use core::{
+    fmt,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use nom::{
+    IResult, Parser,
+    character::complete::{anychar, line_ending},
+    combinator::map_opt,
+    multi::{count, many0, many1},
+    sequence::preceded,
+};
+
+use crate::parse::{Mode, parse_all};
+
+/// Row and column of a cell in a [`Grid`].
+pub type Position = (usize, usize);
+
+const NEIGHBORS_4: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+const NEIGHBORS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Cell of a [`Grid`] written as a single character of the puzzle input.
+pub trait Cell: Sized {
+    fn from_char(c: char) -> Option<Self>;
+
+    fn to_char(&self) -> char;
+}
+
+/// Rectangular grid of cells stored row after row.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+}
+
+impl<T> Grid<T> {
+    /// Grid of the rows in `rows`, unless they differ in length.
+    pub fn from_rows(rows: impl IntoIterator<Item = Vec<T>>) -> Option<Self> {
+        let mut rows = rows.into_iter().peekable();
+        let width = rows.peek().map_or(0, Vec::len);
+        let mut cells = Vec::new();
+        for row in rows {
+            if row.len() != width {
+                return None;
+            }
+            cells.extend(row);
+        }
+        Some(Self { cells, width })
+    }
+
+    pub fn filled(width: usize, height: usize, cell: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![cell; width * height],
+            width,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.len().checked_div(self.width).unwrap_or(0)
+    }
+
+    pub fn get(&self, (row, column): Position) -> Option<&T> {
+        self.index_of((row, column)).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.index_of(position).map(|i| &mut self.cells[i])
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Position> + use<T> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i / width, i % width))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        self.rows().nth(row)
+    }
+
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &[T]> + DoubleEndedIterator {
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    pub fn column(&self, column: usize) -> impl Iterator<Item = &T> {
+        let cells = if column < self.width {
+            &self.cells[column..]
+        } else {
+            &[]
+        };
+        cells.iter().step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|column| self.column(column))
+    }
+
+    /// Positions next to `position` horizontally or vertically, within the grid.
+    pub fn neighbors_4(&self, position: Position) -> impl Iterator<Item = Position> + use<T> {
+        self.neighbors(position, &NEIGHBORS_4)
+    }
+
+    /// Positions next to `position` in any direction including diagonally, within the grid.
+    pub fn neighbors_8(&self, position: Position) -> impl Iterator<Item = Position> + use<T> {
+        self.neighbors(position, &NEIGHBORS_8)
+    }
+
+    /// Grid with the rows and columns swapped.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_rows(
+            self.columns()
+                .map(|column| column.cloned().collect::<Vec<_>>()),
+        )
+        .expect("columns to be as long as the grid is high")
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+        }
+    }
+
+    fn neighbors(
+        &self,
+        (row, column): Position,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Position> + use<T> {
+        let (width, height) = (self.width, self.height());
+        offsets
+            .iter()
+            .filter_map(move |&(row_offset, column_offset)| {
+                let row = row
+                    .checked_add_signed(row_offset)
+                    .filter(|&row| row < height)?;
+                let column = column
+                    .checked_add_signed(column_offset)
+                    .filter(|&column| column < width)?;
+                Some((row, column))
+            })
+    }
+
+    fn index_of(&self, (row, column): Position) -> Option<usize> {
+        (row < self.height() && column < self.width).then(|| row * self.width + column)
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &Self::Output {
+        self.get(position)
+            .unwrap_or_else(|| panic!("position {position:?} to be within the grid"))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut Self::Output {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("position {position:?} to be within the grid"))
+    }
+}
+
+/// Parses a grid of [`Cell`]s, one row per line, up to the first row that is
+/// not as long as the first one.
+pub fn grid<T: Cell>(input: &str) -> IResult<&str, Grid<T>> {
+    let (input, first) = many1(cell).parse(input)?;
+    let width = first.len();
+    let (input, rows) = many0(preceded(line_ending, count(cell, width))).parse(input)?;
+    let cells = first
+        .into_iter()
+        .chain(rows.into_iter().flatten())
+        .collect();
+    Ok((input, Grid { cells, width }))
+}
+
+fn cell<T: Cell>(input: &str) -> IResult<&str, T> {
+    map_opt(anychar, T::from_char).parse(input)
+}
+
+impl<T: Cell> FromStr for Grid<T> {
+    type Err = miette::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let grid = parse_all(grid, s, Mode::Strict, "parse grid")?;
+        Ok(grid)
+    }
+}
+
+/// Writes the grid back in the format of the puzzle input, without a final newline.
+impl<T: Cell> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            row.iter()
+                .try_for_each(|cell| write!(f, "{}", cell.to_char()))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::ParseError;
+
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    enum Tile {
+        Wall,
+        Floor,
+    }
+
+    impl Cell for Tile {
+        fn from_char(c: char) -> Option<Self> {
+            match c {
+                '#' => Some(Self::Wall),
+                '.' => Some(Self::Floor),
+                _ => None,
+            }
+        }
+
+        fn to_char(&self) -> char {
+            match self {
+                Self::Wall => '#',
+                Self::Floor => '.',
+            }
+        }
+    }
+
+    const MAP: &str = "\
+#..
+.#.
+##.";
+
+    #[test]
+    fn round_trips() -> miette::Result<()> {
+        let grid = MAP.parse::<Grid<Tile>>()?;
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid[(2, 1)], Tile::Wall);
+        assert_eq!(grid.to_string(), MAP);
+        let err = "#.\n#".parse::<Grid<Tile>>().unwrap_err();
+        let err = err.downcast_ref::<ParseError>().expect("a parse error");
+        assert_eq!(
+            (err.to_string(), err.offset()),
+            ("parse grid".to_owned(), 3)
+        );
+        assert!("#x".parse::<Grid<Tile>>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn neighbors_stay_within_bounds() -> miette::Result<()> {
+        let grid = MAP.parse::<Grid<Tile>>()?;
+        assert_eq!(
+            grid.neighbors_4((0, 0)).collect::<Vec<_>>(),
+            [(0, 1), (1, 0)]
+        );
+        assert_eq!(
+            grid.neighbors_8((0, 2)).collect::<Vec<_>>(),
+            [(0, 1), (1, 1), (1, 2)]
+        );
+        assert_eq!(grid.neighbors_8((1, 1)).count(), 8);
+        Ok(())
+    }
+
+    #[test]
+    fn views_rows_and_columns() -> miette::Result<()> {
+        let grid = MAP.parse::<Grid<Tile>>()?;
+        assert_eq!(
+            grid.row(1),
+            Some(&[Tile::Floor, Tile::Wall, Tile::Floor][..])
+        );
+        assert_eq!(grid.row(3), None);
+        let column = grid.column(0).copied().collect::<Vec<_>>();
+        assert_eq!(column, [Tile::Wall, Tile::Floor, Tile::Wall]);
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(grid.transpose().to_string(), "#.#\n.##\n...");
+        assert_eq!(grid.transpose().transpose(), grid);
+        Ok(())
+    }
+}
@@ -0,0 +1,193 @@
+use std::{
+    env, fmt,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use miette::{Context, IntoDiagnostic, miette};
+
+const INPUT_FILE: &str = "input.txt";
+const EXAMPLES_DIR: &str = "examples";
+const STDIN_ARG: &str = "-";
+
+const BYTE_ORDER_MARK: char = '\u{feff}';
+
+/// How much of a puzzle input is normalized before a day parses it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Normalization {
+    /// Line endings become `\n`, a byte order mark is dropped, whitespace
+    /// at the end of lines is trimmed and the input ends in a single newline.
+    #[default]
+    Full,
+    /// Like [`Normalization::Full`], but keeps the whitespace at the end of
+    /// lines for formats where it is significant.
+    KeepWhitespace,
+}
+
+/// Where a puzzle input is read from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    /// The `input.txt` that sits next to the day crate in `day_dir`.
+    pub fn default_for(day_dir: impl AsRef<Path>) -> Self {
+        Self::File(input_path(day_dir))
+    }
+
+    /// The example input of `part`, kept in the `examples` directory of the day crate.
+    pub fn example_for(day_dir: impl AsRef<Path>, part: u8) -> Self {
+        Self::File(example_path(day_dir, part))
+    }
+
+    /// Resolves the input from the first command line argument, if any.
+    ///
+    /// `-` reads from stdin, any other argument is a path to the input file
+    /// and no argument falls back to [`Source::default_for`].
+    pub fn from_args(day_dir: impl AsRef<Path>) -> Self {
+        Self::from_arg(day_dir, env::args().nth(1))
+    }
+
+    pub fn from_arg(day_dir: impl AsRef<Path>, arg: Option<String>) -> Self {
+        match arg.as_deref() {
+            None => Self::default_for(day_dir),
+            Some(STDIN_ARG) => Self::Stdin,
+            Some(path) => Self::File(PathBuf::from(path)),
+        }
+    }
+
+    pub fn read(&self) -> miette::Result<String> {
+        match self {
+            Self::File(path) => read_file(path),
+            Self::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .into_diagnostic()
+                    .context("read puzzle input from stdin")?;
+                Ok(input)
+            }
+        }
+    }
+
+    /// Reads the input like [`Source::read`], with none when its file does not exist.
+    pub fn read_if_exists(&self) -> miette::Result<Option<String>> {
+        match self {
+            Self::File(path) => match std::fs::read_to_string(path) {
+                Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+                result => result.map(Some).map_err(|err| read_error(path, err)),
+            },
+            Self::Stdin => self.read().map(Some),
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::File(path) => path.display().fmt(f),
+            Self::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+/// Normalizes `input` so that days parse files saved on any platform the same way.
+pub fn normalize(input: &str, normalization: Normalization) -> String {
+    let input = input.strip_prefix(BYTE_ORDER_MARK).unwrap_or(input);
+    let mut normalized = String::with_capacity(input.len() + 1);
+    for line in input.lines() {
+        let line = match normalization {
+            Normalization::Full => line.trim_end(),
+            Normalization::KeepWhitespace => line,
+        };
+        normalized.push_str(line);
+        normalized.push('\n');
+    }
+    let content = normalized.trim_end_matches('\n').len();
+    normalized.truncate(content);
+    if content > 0 {
+        normalized.push('\n');
+    }
+    normalized
+}
+
+/// Path of the real puzzle input of the day crate in `day_dir`.
+pub fn input_path(day_dir: impl AsRef<Path>) -> PathBuf {
+    day_dir.as_ref().join(INPUT_FILE)
+}
+
+/// Path of the example input of `part` of the day crate in `day_dir`.
+pub fn example_path(day_dir: impl AsRef<Path>, part: u8) -> PathBuf {
+    examples_dir(day_dir).join(format!("part-{part}.txt"))
+}
+
+/// Path of the answer to the example input of `part` of the day crate in `day_dir`.
+pub fn example_answer_path(day_dir: impl AsRef<Path>, part: u8) -> PathBuf {
+    examples_dir(day_dir).join(format!("part-{part}.answer"))
+}
+
+fn examples_dir(day_dir: impl AsRef<Path>) -> PathBuf {
+    day_dir.as_ref().join(EXAMPLES_DIR)
+}
+
+fn read_file(path: &Path) -> miette::Result<String> {
+    std::fs::read_to_string(path).map_err(|err| read_error(path, err))
+}
+
+fn read_error(path: &Path, err: io::Error) -> miette::Report {
+    match err.kind() {
+        io::ErrorKind::NotFound => miette!(
+            help = "save your puzzle input there, pass another path as an argument or use `-` to read stdin",
+            "puzzle input {} does not exist",
+            path.display()
+        ),
+        _ => miette!("{err}").wrap_err(format!("read puzzle input {}", path.display())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_to_day_input() {
+        let source = Source::from_arg("day-08", None);
+        assert_eq!(source, Source::File(PathBuf::from("day-08/input.txt")));
+    }
+
+    #[test]
+    fn argument_overrides_input() {
+        let source = Source::from_arg("day-08", Some("other.txt".into()));
+        assert_eq!(source, Source::File(PathBuf::from("other.txt")));
+        let source = Source::from_arg("day-08", Some("-".into()));
+        assert_eq!(source, Source::Stdin);
+    }
+
+    #[test]
+    fn normalizes_line_endings() {
+        let input = "\u{feff}1,2  \r\n3,4\r\n\r\n";
+        assert_eq!(normalize(input, Normalization::Full), "1,2\n3,4\n");
+        assert_eq!(normalize("1,2\n3,4", Normalization::Full), "1,2\n3,4\n");
+        assert_eq!(normalize("\n\n", Normalization::Full), "");
+    }
+
+    #[test]
+    fn keeps_significant_whitespace() {
+        let input = "12 3 \r\n*  + \n\n";
+        assert_eq!(
+            normalize(input, Normalization::KeepWhitespace),
+            "12 3 \n*  + \n"
+        );
+    }
+
+    #[test]
+    fn missing_input_is_reported() {
+        let err = Source::default_for("does-not-exist").read().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "puzzle input does-not-exist/input.txt does not exist"
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use core::{fmt, ops::RangeInclusive};
+
+/// Set of integers kept as sorted, disjoint inclusive ranges.
+///
+/// Ranges that overlap or touch are merged as they are inserted,
+/// so every integer up to `u64::MAX` can be covered.
+#[derive(Clone, Default, Hash, PartialEq, Eq)]
+pub struct IntervalSet {
+    ranges: Vec<(u64, u64)>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the integers of `range`, merging it with the ranges it overlaps or touches.
+    pub fn insert(&mut self, range: RangeInclusive<u64>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        let first = self
+            .ranges
+            .partition_point(|&(_, other_end)| other_end.saturating_add(1) < start);
+        let last = self
+            .ranges
+            .partition_point(|&(other_start, _)| other_start <= end.saturating_add(1));
+        if let Some(&(other_start, _)) = self.ranges[first..last].first() {
+            start = start.min(other_start);
+        }
+        if let Some(&(_, other_end)) = self.ranges[first..last].last() {
+            end = end.max(other_end);
+        }
+        self.ranges.splice(first..last, [(start, end)]);
+    }
+
+    pub fn contains(&self, value: u64) -> bool {
+        let i = self.ranges.partition_point(|&(_, end)| end < value);
+        self.ranges.get(i).is_some_and(|&(start, _)| start <= value)
+    }
+
+    /// Number of integers in the set.
+    pub fn len(&self) -> u128 {
+        self.ranges
+            .iter()
+            .map(|&(start, end)| u128::from(end - start) + 1)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The disjoint ranges of the set in increasing order.
+    pub fn ranges(&self) -> impl ExactSizeIterator<Item = RangeInclusive<u64>> + '_ {
+        self.ranges.iter().map(|&(start, end)| start..=end)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        other.ranges().for_each(|range| union.insert(range));
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (mut i, mut j) = (0, 0);
+        let mut ranges = Vec::new();
+        while let (Some(&(start, end)), Some(&(other_start, other_end))) =
+            (self.ranges.get(i), other.ranges.get(j))
+        {
+            let (overlap_start, overlap_end) = (start.max(other_start), end.min(other_end));
+            if overlap_start <= overlap_end {
+                ranges.push((overlap_start, overlap_end));
+            }
+            if end < other_end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        for &(start, end) in &self.ranges {
+            let mut next = Some(start);
+            let first = other
+                .ranges
+                .partition_point(|&(_, other_end)| other_end < start);
+            for &(other_start, other_end) in &other.ranges[first..] {
+                let Some(current) = next else { break };
+                if other_start > end {
+                    break;
+                }
+                if other_start > current {
+                    ranges.push((current, other_start - 1));
+                }
+                next = other_end.checked_add(1).filter(|&after| after <= end);
+            }
+            if let Some(current) = next {
+                ranges.push((current, end));
+            }
+        }
+        Self { ranges }
+    }
+}
+
+impl FromIterator<RangeInclusive<u64>> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<u64>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        iter.into_iter().for_each(|range| set.insert(range));
+        set
+    }
+}
+
+impl fmt::Debug for IntervalSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.ranges()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(set: &IntervalSet) -> Vec<RangeInclusive<u64>> {
+        set.ranges().collect()
+    }
+
+    #[test]
+    fn merges_overlapping_and_touching_ranges() {
+        let set = [3..=5, 10..=14, 16..=20, 12..=18, 6..=6]
+            .into_iter()
+            .collect::<IntervalSet>();
+        assert_eq!(ranges(&set), [3..=6, 10..=20]);
+        assert_eq!(set.len(), 15);
+        assert!(set.contains(6) && set.contains(10) && set.contains(20));
+        assert!(!set.contains(2) && !set.contains(7) && !set.contains(21));
+    }
+
+    #[test]
+    fn covers_the_whole_range() {
+        let set = [u64::MAX - 1..=u64::MAX, 0..=u64::MAX - 2]
+            .into_iter()
+            .collect::<IntervalSet>();
+        assert_eq!(ranges(&set), [0..=u64::MAX]);
+        assert_eq!(set.len(), 1 << 64);
+        assert!(set.contains(u64::MAX));
+    }
+
+    #[test]
+    fn combines_sets() {
+        let a = [0..=10, 20..=30, 40..=u64::MAX]
+            .into_iter()
+            .collect::<IntervalSet>();
+        let b = [5..=25, 35..=45, u64::MAX..=u64::MAX]
+            .into_iter()
+            .collect::<IntervalSet>();
+        assert_eq!(ranges(&a.union(&b)), [0..=30, 35..=u64::MAX]);
+        assert_eq!(
+            ranges(&a.intersection(&b)),
+            [5..=10, 20..=25, 40..=45, u64::MAX..=u64::MAX]
+        );
+        assert_eq!(
+            ranges(&a.difference(&b)),
+            [0..=4, 26..=30, 46..=u64::MAX - 1]
+        );
+        assert_eq!(ranges(&b.difference(&a)), [11..=19, 35..=39]);
+        assert!(a.difference(&a).is_empty());
+    }
+}
@@ -0,0 +1,18 @@
+mod answer;
+mod answers;
+pub mod bitset;
+pub mod disjoint_set;
+mod example;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod parse;
+pub mod polyomino;
+mod solution;
+
+pub use answer::Answer;
+pub use answers::{Answers, WrongAnswers};
+pub use example::Example;
+pub use solution::{DynSolution, Solution};
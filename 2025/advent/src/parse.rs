@@ -0,0 +1,349 @@
+use core::{fmt, ops::Range};
+
+use miette::{Diagnostic, LabeledSpan, NamedSource, Report, SourceCode, SourceSpan};
+use nom::{Parser, error::ErrorKind};
+
+/// Name of the source of a [`ParseError`] until the runner knows where the input came from.
+const DEFAULT_SOURCE_NAME: &str = "input";
+
+const UNPARSED: &str = "unexpected input";
+
+/// How much of a puzzle input a parser has to account for.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// All of the input has to parse, apart from the line endings it ends with.
+    #[default]
+    Strict,
+    /// Lines that do not parse and input left after the parser are skipped.
+    Lenient,
+}
+
+/// Failure of a nom parser on a puzzle input, highlighting where in the input it failed.
+#[derive(Debug)]
+pub struct ParseError {
+    context: String,
+    source: NamedSource<String>,
+    labels: Vec<(SourceSpan, String)>,
+}
+
+impl ParseError {
+    /// Locates `err` of a parser that was given `input`, described by `context`.
+    pub fn new(
+        input: &str,
+        err: nom::Err<nom::error::Error<&str>>,
+        context: impl Into<String>,
+    ) -> Self {
+        Self::regions(input, [failure(input, 0, err)], context)
+    }
+
+    /// Points at every region of `input` that could not be parsed, each with its label.
+    pub fn regions(
+        input: &str,
+        regions: impl IntoIterator<Item = (Range<usize>, String)>,
+        context: impl Into<String>,
+    ) -> Self {
+        Self {
+            context: context.into(),
+            source: NamedSource::new(DEFAULT_SOURCE_NAME, input.to_owned()),
+            labels: regions
+                .into_iter()
+                .map(|(region, label)| (SourceSpan::from(region), label))
+                .collect(),
+        }
+    }
+
+    /// Names the input shown with the error, usually after the file it was read from.
+    pub fn rename(&mut self, name: impl AsRef<str>) {
+        self.source = NamedSource::new(name, self.source.inner().clone());
+    }
+
+    /// Offset of the first region that could not be parsed.
+    pub fn offset(&self) -> usize {
+        self.labels.first().map_or(0, |(span, _)| span.offset())
+    }
+
+    /// Regions of the input that could not be parsed.
+    pub fn spans(&self) -> impl Iterator<Item = Range<usize>> + '_ {
+        self.labels
+            .iter()
+            .map(|(span, _)| span.offset()..span.offset() + span.len())
+    }
+}
+
+/// Parses the whole of `input` with `parser`, described by `context`.
+///
+/// A line that stops the parser is left out and the rest of the input parsed
+/// again, so that [`Mode::Strict`] reports every such line and [`Mode::Lenient`]
+/// skips them.
+pub fn parse_all<O>(
+    mut parser: impl for<'a> Parser<&'a str, Output = O, Error = nom::error::Error<&'a str>>,
+    input: &str,
+    mode: Mode,
+    context: &str,
+) -> Result<O, ParseError> {
+    let mut lines = line_ranges(input).collect::<Vec<_>>();
+    let mut failures = Vec::new();
+    let output = loop {
+        let kept = lines
+            .iter()
+            .map(|line| &input[line.clone()])
+            .collect::<String>();
+        let (region, label) = match parser.parse(&kept) {
+            Ok((rest, output)) => {
+                let unparsed = rest.trim_matches(['\r', '\n']);
+                if unparsed.is_empty() {
+                    break output;
+                }
+                let at =
+                    offset(&kept, rest) + rest.len() - rest.trim_start_matches(['\r', '\n']).len();
+                let end = kept[at..]
+                    .find(['\r', '\n'])
+                    .map_or(kept.len(), |end| at + end);
+                (at..end, UNPARSED.to_owned())
+            }
+            Err(err) => failure(&kept, 0, err),
+        };
+        let start = original(&lines, region.start);
+        let line = kept_line(&lines, region.start);
+        failures.push((start..start + region.len(), label));
+        let Some(line) = line else {
+            return Err(ParseError::regions(input, failures, context));
+        };
+        lines.remove(line);
+    };
+    match mode {
+        Mode::Strict if !failures.is_empty() => {
+            return Err(ParseError::regions(input, failures, context));
+        }
+        Mode::Strict => {}
+        Mode::Lenient => {
+            for (region, label) in failures {
+                tracing::warn!(?region, "{context}: skipped line, {label}");
+            }
+        }
+    }
+    Ok(output)
+}
+
+/// Parses each of `items` with `parser`, described by `context`.
+///
+/// The items have to be slices of `input`, such as its lines. In [`Mode::Strict`]
+/// every item has to parse completely and all that do not are reported together,
+/// in [`Mode::Lenient`] they are skipped.
+pub fn parse_each<'a, O>(
+    mut parser: impl Parser<&'a str, Output = O, Error = nom::error::Error<&'a str>>,
+    input: &'a str,
+    items: impl IntoIterator<Item = &'a str>,
+    mode: Mode,
+    context: &str,
+) -> Result<Vec<O>, ParseError> {
+    let mut outputs = Vec::new();
+    let mut failures = Vec::new();
+    for item in items {
+        let start = (item.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|&start| start + item.len() <= input.len())
+            .expect("items to be slices of the input");
+        match parser.parse(item) {
+            Ok(("", output)) => outputs.push(output),
+            Ok((_, output)) if mode == Mode::Lenient => outputs.push(output),
+            Ok((rest, _)) => {
+                let rest_start = start + offset(item, rest);
+                failures.push((rest_start..start + item.len(), UNPARSED.to_owned()));
+            }
+            Err(err) => failures.push(failure(item, start, err)),
+        }
+    }
+    match mode {
+        Mode::Strict if !failures.is_empty() => {
+            return Err(ParseError::regions(input, failures, context));
+        }
+        Mode::Strict => {}
+        Mode::Lenient => {
+            for (region, label) in failures {
+                tracing::warn!(?region, "{context}: skipped item, {label}");
+            }
+        }
+    }
+    Ok(outputs)
+}
+
+/// Names the input of the [`ParseError`] somewhere in `report`, if there is one.
+pub fn name_source(mut report: Report, name: impl AsRef<str>) -> Report {
+    if let Some(err) = report.downcast_mut::<ParseError>() {
+        err.rename(name);
+    }
+    report
+}
+
+/// Regions of the lines of `input`, each with its line ending.
+fn line_ranges(input: &str) -> impl Iterator<Item = Range<usize>> + '_ {
+    input.split_inclusive('\n').scan(0, |start, line| {
+        let region = *start..*start + line.len();
+        *start = region.end;
+        Some(region)
+    })
+}
+
+/// Index in `lines` of the line at offset `at` of the text they make up.
+fn kept_line(lines: &[Range<usize>], at: usize) -> Option<usize> {
+    let mut start = 0;
+    lines.iter().position(|line| {
+        start += line.len();
+        at < start
+    })
+}
+
+/// Offset in the original input of offset `at` of the text that `lines` make up.
+fn original(lines: &[Range<usize>], at: usize) -> usize {
+    let mut start = 0;
+    for line in lines {
+        if at < start + line.len() {
+            return line.start + at - start;
+        }
+        start += line.len();
+    }
+    lines.last().map_or(at, |line| line.end + at - start)
+}
+
+/// Region and label of `err` of a parser given `input`, which starts at `start`.
+fn failure(
+    input: &str,
+    start: usize,
+    err: nom::Err<nom::error::Error<&str>>,
+) -> (Range<usize>, String) {
+    let (at, label) = match err {
+        nom::Err::Error(err) | nom::Err::Failure(err) => {
+            (offset(input, err.input), expected(err.code))
+        }
+        nom::Err::Incomplete(_) => (input.len(), "expected more input".to_owned()),
+    };
+    let length = input[at..].chars().next().map_or(0, char::len_utf8);
+    (start + at..start + at + length, label)
+}
+
+/// Offset of `rest` in `input`, which nom always leaves a suffix of it.
+fn offset(input: &str, rest: &str) -> usize {
+    input.len() - rest.len().min(input.len())
+}
+
+fn expected(kind: ErrorKind) -> String {
+    match kind {
+        ErrorKind::Tag => "expected different text here".to_owned(),
+        ErrorKind::Char | ErrorKind::OneOf | ErrorKind::NoneOf => "unexpected character".to_owned(),
+        ErrorKind::Digit | ErrorKind::HexDigit | ErrorKind::OctDigit | ErrorKind::BinDigit => {
+            "expected a number".to_owned()
+        }
+        ErrorKind::Alpha => "expected letters".to_owned(),
+        ErrorKind::AlphaNumeric => "expected letters or digits".to_owned(),
+        ErrorKind::Space | ErrorKind::MultiSpace => "expected whitespace".to_owned(),
+        ErrorKind::CrLf => "expected a line ending".to_owned(),
+        ErrorKind::Eof => "expected the end of the input".to_owned(),
+        ErrorKind::Alt => "expected one of the alternatives".to_owned(),
+        ErrorKind::SeparatedList | ErrorKind::Many1 | ErrorKind::Many1Count => {
+            "expected at least one item".to_owned()
+        }
+        ErrorKind::MapRes | ErrorKind::MapOpt | ErrorKind::Verify => "invalid value".to_owned(),
+        other => format!("{} failed", other.description()),
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.context.fmt(f)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl Diagnostic for ParseError {
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        Some(&self.source)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        Some(Box::new(self.labels.iter().map(|(span, label)| {
+            LabeledSpan::new_with_span(Some(label.clone()), *span)
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{
+        IResult, Parser,
+        bytes::complete::tag,
+        character::complete::{self, line_ending},
+        multi::separated_list1,
+        sequence::separated_pair,
+    };
+
+    use super::*;
+
+    fn pair(input: &str) -> IResult<&str, (u32, u32)> {
+        separated_pair(complete::u32, tag(","), complete::u32).parse(input)
+    }
+
+    fn pairs(input: &str) -> IResult<&str, Vec<(u32, u32)>> {
+        separated_list1(line_ending, pair).parse(input)
+    }
+
+    #[test]
+    fn points_at_failure() {
+        let input = "12,34\n56;78\n";
+        let err = pair(&input[6..]).unwrap_err();
+        let err = ParseError::new(input, err, "parse pairs");
+        assert_eq!(err.to_string(), "parse pairs");
+        assert_eq!(err.offset(), 8);
+        let label = err.labels().unwrap().next().unwrap();
+        assert_eq!(label.label(), Some("expected different text here"));
+        assert_eq!(label.len(), 1);
+    }
+
+    #[test]
+    fn names_source_through_context() {
+        use miette::Context;
+
+        let input = "x";
+        let err = ParseError::new(input, pair(input).unwrap_err(), "parse pairs");
+        let report = Err::<(), _>(err)
+            .context("solve day 01 part 1")
+            .unwrap_err();
+        let report = name_source(report, "day-01/input.txt");
+        let err = report.downcast_ref::<ParseError>().unwrap();
+        assert_eq!(err.source.name(), "day-01/input.txt");
+        assert_eq!(err.labels[0].1, "expected a number");
+    }
+
+    #[test]
+    fn strict_rejects_trailing_input() {
+        let input = "1,2\n3,4\n5;6\n";
+        let err = parse_all(pairs, input, Mode::Strict, "parse pairs").unwrap_err();
+        assert_eq!(err.spans().collect::<Vec<_>>(), vec![8..11]);
+        let parsed = parse_all(pairs, input, Mode::Lenient, "parse pairs").unwrap();
+        assert_eq!(parsed, [(1, 2), (3, 4)]);
+        let parsed = parse_all(pairs, "1,2\n3,4\n\n", Mode::Strict, "parse pairs").unwrap();
+        assert_eq!(parsed, [(1, 2), (3, 4)]);
+    }
+
+    #[test]
+    fn parsing_resumes_after_bad_lines() {
+        let input = "1,2\nx\n3,4\n5;6\n7,8\n";
+        let err = parse_all(pairs, input, Mode::Strict, "parse pairs").unwrap_err();
+        assert_eq!(err.spans().collect::<Vec<_>>(), [4..5, 10..13]);
+        let parsed = parse_all(pairs, input, Mode::Lenient, "parse pairs").unwrap();
+        assert_eq!(parsed, [(1, 2), (3, 4), (7, 8)]);
+        let err = parse_all(pairs, "x\n1,2\n3,\n", Mode::Strict, "parse pairs").unwrap_err();
+        assert_eq!(err.spans().collect::<Vec<_>>(), [0..1, 6..8]);
+        assert!(parse_all(pairs, "x\ny\n", Mode::Lenient, "parse pairs").is_err());
+    }
+
+    #[test]
+    fn strict_reports_every_skipped_line() {
+        let input = "1,2\nx\n3,4\n5,6;\n";
+        let err = parse_each(pair, input, input.lines(), Mode::Strict, "parse pairs").unwrap_err();
+        assert_eq!(err.spans().collect::<Vec<_>>(), [4..5, 13..14]);
+        let parsed = parse_each(pair, input, input.lines(), Mode::Lenient, "parse pairs").unwrap();
+        assert_eq!(parsed, [(1, 2), (3, 4), (5, 6)]);
+    }
+}
@@ -0,0 +1,187 @@
+use core::fmt;
+
+use crate::grid::{Grid, Position};
+
+/// Shape made of cells, kept normalized: in reading order and moved so that
+/// its topmost row and leftmost column are both 0.
+///
+/// Two shapes that only differ in where they were drawn are equal.
+#[derive(Clone, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Polyomino {
+    cells: Vec<Position>,
+}
+
+impl Polyomino {
+    /// Shape of the cells, wherever they are, each counted once.
+    pub fn new(cells: impl IntoIterator<Item = (isize, isize)>) -> Self {
+        let cells = cells.into_iter().collect::<Vec<_>>();
+        let min_row = cells.iter().map(|&(row, _)| row).min().unwrap_or(0);
+        let min_column = cells.iter().map(|&(_, column)| column).min().unwrap_or(0);
+        let mut cells = cells
+            .into_iter()
+            .map(|(row, column)| ((row - min_row) as usize, (column - min_column) as usize))
+            .collect::<Vec<_>>();
+        cells.sort_unstable();
+        cells.dedup();
+        Self { cells }
+    }
+
+    /// Shape of the cells of `grid` for which `filled` holds.
+    pub fn from_grid<T>(grid: &Grid<T>, filled: impl Fn(&T) -> bool) -> Self {
+        Self::new(
+            grid.iter()
+                .filter(|&(_, cell)| filled(cell))
+                .map(|((row, column), _)| (row as isize, column as isize)),
+        )
+    }
+
+    /// The cells in reading order.
+    pub fn cells(&self) -> &[Position] {
+        &self.cells
+    }
+
+    /// Number of cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Width of the bounding box.
+    pub fn width(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|&(_, column)| column + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Height of the bounding box.
+    pub fn height(&self) -> usize {
+        self.cells.last().map_or(0, |&(row, _)| row + 1)
+    }
+
+    /// The shape turned a quarter clockwise.
+    pub fn rotate(&self) -> Self {
+        Self::new(
+            self.cells
+                .iter()
+                .map(|&(row, column)| (column as isize, -(row as isize))),
+        )
+    }
+
+    /// The shape mirrored left to right.
+    pub fn reflect(&self) -> Self {
+        Self::new(
+            self.cells
+                .iter()
+                .map(|&(row, column)| (row as isize, -(column as isize))),
+        )
+    }
+
+    /// The distinct shapes among all rotations and reflections, in order.
+    pub fn variants(&self) -> Vec<Self> {
+        let mut variants = Vec::with_capacity(8);
+        let mut variant = self.clone();
+        for _ in 0..4 {
+            variants.push(variant.reflect());
+            let rotated = variant.rotate();
+            variants.push(variant);
+            variant = rotated;
+        }
+        variants.sort_unstable();
+        variants.dedup();
+        variants
+    }
+
+    /// The same variant for every rotation and reflection of the shape, to tell
+    /// whether two shapes are the same piece.
+    pub fn canonical(&self) -> Self {
+        self.variants().into_iter().next().unwrap_or_default()
+    }
+
+    /// Each row as a bitmask with bit `column` set for the cells in that column,
+    /// unless the shape is wider than 64 cells.
+    pub fn row_masks(&self) -> Option<Vec<u64>> {
+        if self.width() > u64::BITS as usize {
+            return None;
+        }
+        let mut masks = vec![0; self.height()];
+        self.cells
+            .iter()
+            .for_each(|&(row, column)| masks[row] |= 1 << column);
+        Some(masks)
+    }
+}
+
+/// Draws the bounding box with `#` for the cells and `.` elsewhere, without a final newline.
+impl fmt::Display for Polyomino {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut cells = self.cells.iter().peekable();
+        for row in 0..self.height() {
+            if row > 0 {
+                writeln!(f)?;
+            }
+            for column in 0..self.width() {
+                let filled = cells.next_if_eq(&&(row, column)).is_some();
+                write!(f, "{}", if filled { '#' } else { '.' })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Polyomino {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(&self.cells).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polyomino(drawing: &str) -> Polyomino {
+        let cells = drawing.lines().enumerate().flat_map(|(row, line)| {
+            line.char_indices()
+                .filter(|&(_, c)| c == '#')
+                .map(move |(column, _)| (row as isize, column as isize))
+        });
+        Polyomino::new(cells)
+    }
+
+    #[test]
+    fn normalizes_to_the_origin() {
+        let shape = polyomino("....\n..##\n.##.");
+        assert_eq!(shape.cells(), [(0, 1), (0, 2), (1, 0), (1, 1)]);
+        assert_eq!((shape.len(), shape.width(), shape.height()), (4, 3, 2));
+        assert_eq!(shape.to_string(), ".##\n##.");
+        assert_eq!(shape.row_masks(), Some(vec![0b110, 0b011]));
+    }
+
+    #[test]
+    fn rotates_and_reflects() {
+        let shape = polyomino("#..\n###");
+        assert_eq!(shape.rotate().to_string(), "##\n#.\n#.");
+        assert_eq!(shape.reflect().to_string(), "..#\n###");
+        assert_eq!(shape.rotate().rotate().rotate().rotate(), shape);
+        assert_eq!(shape.variants().len(), 8);
+        assert_eq!(polyomino("##\n##").variants().len(), 1);
+        assert_eq!(polyomino("###").variants().len(), 2);
+        assert_eq!(polyomino(".#.\n###").variants().len(), 4);
+    }
+
+    #[test]
+    fn canonical_forms_match_for_the_same_piece() {
+        let shape = polyomino("###\n##.\n##.");
+        for variant in shape.variants() {
+            assert_eq!(variant.canonical(), shape.canonical());
+        }
+        assert_ne!(
+            polyomino("##.\n.##").canonical(),
+            polyomino("###\n.#.").canonical()
+        );
+    }
+}
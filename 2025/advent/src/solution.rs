@@ -0,0 +1,61 @@
+use std::any::Any;
+
+use crate::{
+    Answer,
+    input::{Normalization, normalize},
+    parse::Mode,
+};
+
+/// One part of a puzzle, split into parsing the input and solving the parsed model.
+pub trait Solution {
+    type Input;
+    type Output: Into<Answer>;
+
+    /// Normalization applied to the input before it is parsed.
+    const NORMALIZATION: Normalization = Normalization::Full;
+
+    fn parse(&self, input: &str, mode: Mode) -> miette::Result<Self::Input>;
+
+    fn solve(&self, input: &Self::Input) -> miette::Result<Self::Output>;
+
+    /// Solves `input`, which has to parse in [`Mode::Strict`].
+    fn run(&self, input: &str) -> miette::Result<Self::Output> {
+        self.run_with(input, Mode::Strict)
+    }
+
+    fn run_with(&self, input: &str, mode: Mode) -> miette::Result<Self::Output> {
+        let input = self.parse(&normalize(input, Self::NORMALIZATION), mode)?;
+        self.solve(&input)
+    }
+}
+
+/// Object safe [`Solution`] with its parsed model type erased,
+/// so that solutions of different days can be driven the same way.
+pub trait DynSolution: Sync {
+    fn parse(&self, input: &str, mode: Mode) -> miette::Result<Box<dyn Any>>;
+
+    fn solve(&self, input: &dyn Any) -> miette::Result<Answer>;
+
+    fn run(&self, input: &str, mode: Mode) -> miette::Result<Answer> {
+        let input = self.parse(input, mode)?;
+        self.solve(input.as_ref())
+    }
+}
+
+impl<S> DynSolution for S
+where
+    S: Solution + Sync,
+    S::Input: 'static,
+{
+    fn parse(&self, input: &str, mode: Mode) -> miette::Result<Box<dyn Any>> {
+        Solution::parse(self, &normalize(input, S::NORMALIZATION), mode)
+            .map(|input| Box::new(input) as Box<dyn Any>)
+    }
+
+    fn solve(&self, input: &dyn Any) -> miette::Result<Answer> {
+        let input = input
+            .downcast_ref()
+            .expect("input to be parsed by the same solution");
+        Solution::solve(self, input).map(Into::into)
+    }
+}
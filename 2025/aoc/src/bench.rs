@@ -0,0 +1,101 @@
+use std::{
+    fmt,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use advent::{DynSolution, input::Source, parse::Mode};
+
+use crate::Day;
+
+const MAX_ITERATIONS: usize = 10_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputKind {
+    Real,
+    Example,
+}
+
+/// Median timings of one part on one input.
+#[derive(Clone, Copy, Debug)]
+pub struct Benchmark {
+    pub day: u8,
+    pub part: u8,
+    pub input: InputKind,
+    pub parse: Duration,
+    pub solve: Duration,
+    pub iterations: usize,
+}
+
+impl Benchmark {
+    pub fn total(&self) -> Duration {
+        self.parse + self.solve
+    }
+}
+
+/// The inputs of a part that exist on disk, the real one first, or why one
+/// that exists could not be read.
+pub fn inputs(day: &Day, part: u8) -> impl Iterator<Item = miette::Result<(InputKind, String)>> {
+    [
+        (InputKind::Real, Source::default_for(day.dir())),
+        (InputKind::Example, Source::example_for(day.dir(), part)),
+    ]
+    .into_iter()
+    .filter_map(|(kind, source)| {
+        source
+            .read_if_exists()
+            .transpose()
+            .map(|input| input.map(|input| (kind, input)))
+    })
+}
+
+/// Repeatedly parses and solves `input` until `budget` is spent,
+/// timing both phases separately.
+pub fn bench(
+    day: &Day,
+    part: u8,
+    kind: InputKind,
+    input: &str,
+    budget: Duration,
+) -> miette::Result<Benchmark> {
+    let solution = day.part(part)?;
+    let start = Instant::now();
+    let mut samples = Vec::new();
+    while samples.is_empty() || start.elapsed() < budget && samples.len() < MAX_ITERATIONS {
+        samples.push(sample(solution, input)?);
+    }
+    let (mut parse, mut solve): (Vec<_>, Vec<_>) = samples.into_iter().unzip();
+    Ok(Benchmark {
+        day: day.number,
+        part,
+        input: kind,
+        parse: median(&mut parse),
+        solve: median(&mut solve),
+        iterations: parse.len(),
+    })
+}
+
+fn sample(solution: &dyn DynSolution, input: &str) -> miette::Result<(Duration, Duration)> {
+    let start = Instant::now();
+    let parsed = solution.parse(black_box(input), Mode::Strict)?;
+    let parse = start.elapsed();
+    let start = Instant::now();
+    black_box(solution.solve(parsed.as_ref())?);
+    let solve = start.elapsed();
+    Ok((parse, solve))
+}
+
+fn median(samples: &mut [Duration]) -> Duration {
+    samples.sort_unstable();
+    samples[samples.len() / 2]
+}
+
+impl fmt::Display for InputKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Real => "real",
+            Self::Example => "example",
+        }
+        .fmt(f)
+    }
+}
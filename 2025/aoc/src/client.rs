@@ -0,0 +1,111 @@
+use std::{env, path::PathBuf};
+
+use miette::{Context, IntoDiagnostic, miette};
+use ureq::{Agent, Body, http::Response};
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+pub const USER_AGENT: &str = concat!(
+    "github.com/grimerssy/advent ",
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION")
+);
+
+const SESSION_VAR: &str = "AOC_SESSION";
+const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+/// Talks to the Advent of Code website as the user owning the session token.
+pub struct Client {
+    agent: Agent,
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(base_url: impl Into<String>, session: impl Into<String>) -> Self {
+        let agent = Agent::config_builder()
+            .user_agent(USER_AGENT)
+            .http_status_as_error(false)
+            .build()
+            .into();
+        Self {
+            agent,
+            base_url: base_url.into().trim_end_matches('/').to_owned(),
+            session: session.into(),
+        }
+    }
+
+    /// A client for `base_url`, or `$AOC_BASE_URL` falling back to [`BASE_URL`],
+    /// authenticated with the session token from [`session`].
+    pub fn from_env(base_url: Option<String>) -> miette::Result<Self> {
+        let base_url = base_url
+            .or_else(|| env::var(BASE_URL_VAR).ok())
+            .unwrap_or_else(|| BASE_URL.to_owned());
+        Ok(Self::new(base_url, session()?))
+    }
+
+    pub fn input(&self, year: u16, day: u8) -> miette::Result<String> {
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+        let response = self
+            .agent
+            .get(&url)
+            .header("Cookie", &format!("session={}", self.session))
+            .call();
+        read(&url, response)
+    }
+
+    /// Posts `answer` for `part` and returns the response page.
+    pub fn answer(&self, year: u16, day: u8, part: u8, answer: &str) -> miette::Result<String> {
+        let url = format!("{}/{year}/day/{day}/answer", self.base_url);
+        let response = self
+            .agent
+            .post(&url)
+            .header("Cookie", &format!("session={}", self.session))
+            .send_form([("level", part.to_string().as_str()), ("answer", answer)]);
+        read(&url, response)
+    }
+}
+
+fn read(url: &str, response: Result<Response<Body>, ureq::Error>) -> miette::Result<String> {
+    let mut response = response
+        .into_diagnostic()
+        .with_context(|| format!("request {url}"))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(miette!(
+            help = "check that the puzzle is unlocked and the session token is current",
+            "{url} responded with {status}"
+        ));
+    }
+    response
+        .body_mut()
+        .read_to_string()
+        .into_diagnostic()
+        .with_context(|| format!("read response of {url}"))
+}
+
+/// The session token from `$AOC_SESSION` or the `advent/session` config file.
+pub fn session() -> miette::Result<String> {
+    if let Ok(session) = env::var(SESSION_VAR) {
+        return Ok(session.trim().to_owned());
+    }
+    let path = session_path().ok_or_else(|| miette!("find the config directory"))?;
+    std::fs::read_to_string(&path)
+        .map(|session| session.trim().to_owned())
+        .map_err(|_| {
+            miette!(
+                help = format!(
+                    "set ${SESSION_VAR} or save the session cookie of adventofcode.com to {}",
+                    path.display()
+                ),
+                "no session token"
+            )
+        })
+}
+
+fn session_path() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|config| config.join("advent").join("session"))
+}
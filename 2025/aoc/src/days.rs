@@ -0,0 +1,21 @@
+use crate::Day;
+
+pub const DAYS: &[Day] = &[
+    Day::new(1, &day_01::part_1::Part1, &day_01::part_2::Part2),
+    Day::new(2, &day_02::part_1::Part1, &day_02::part_2::Part2),
+    Day::new(4, &day_04::part_1::Part1, &day_04::part_2::Part2),
+    Day::new(5, &day_05::part_1::Part1, &day_05::part_2::Part2),
+    Day::new(6, &day_06::part_1::Part1, &day_06::part_2::Part2),
+    Day::new(7, &day_07::part_1::Part1, &day_07::part_2::Part2),
+    Day::new(
+        8,
+        &day_08::part_1::Part1 {
+            connections: day_08::part_1::CONNECTIONS,
+        },
+        &day_08::part_2::Part2,
+    ),
+    Day::new(9, &day_09::part_1::Part1, &day_09::part_2::Part2),
+    Day::new(10, &day_10::part_1::Part1, &day_10::part_2::Part2),
+    Day::new(11, &day_11::part_1::Part1, &day_11::part_2::PUZZLE),
+    Day::new(12, &day_12::part_1::Part1, &day_12::part_2::Part2),
+];
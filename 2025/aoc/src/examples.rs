@@ -0,0 +1,156 @@
+use std::path::{Path, PathBuf};
+
+use advent::{Answer, Example};
+use miette::{Context, IntoDiagnostic, miette};
+
+const PUZZLE_FILE: &str = "puzzle.html";
+
+/// Path of the puzzle description saved next to the day crate in `day_dir`.
+pub fn puzzle_path(day_dir: impl AsRef<Path>) -> PathBuf {
+    day_dir.as_ref().join(PUZZLE_FILE)
+}
+
+/// Extracts the examples of the puzzle page at `page` and saves them as the
+/// fixtures of the day crate in `day_dir`, returning the parts written.
+pub fn extract_into(page: &Path, day_dir: &Path) -> miette::Result<Vec<(u8, Example)>> {
+    let html = std::fs::read_to_string(page)
+        .into_diagnostic()
+        .with_context(|| format!("read puzzle page {}", page.display()))?;
+    let examples = extract(&html).with_context(|| format!("extract {}", page.display()))?;
+    for (part, example) in &examples {
+        example.save(day_dir, *part)?;
+    }
+    Ok(examples)
+}
+
+/// Examples of every part described on a puzzle page.
+///
+/// Each `<article>` describes one part: its example input is the first
+/// `<pre><code>` block and its answer the last emphasized `<code>`.
+/// A part without an example block of its own reuses the previous input.
+pub fn extract(page: &str) -> miette::Result<Vec<(u8, Example)>> {
+    let mut examples = Vec::new();
+    let mut input = None;
+    for (part, article) in (1..).zip(articles(page)) {
+        if let Some(block) = between(article, "<pre><code>", "</code></pre>").next() {
+            input = Some(text(block));
+        }
+        let input = input
+            .clone()
+            .ok_or_else(|| miette!("part {part} has no example input"))?;
+        let answer = last_emphasized_code(article)
+            .map(|answer| Answer::from(text(answer)))
+            .ok_or_else(|| miette!("part {part} has no example answer"))?;
+        examples.push((part, Example { input, answer }));
+    }
+    if examples.is_empty() {
+        return Err(miette!("no puzzle description found"));
+    }
+    Ok(examples)
+}
+
+fn articles(page: &str) -> impl Iterator<Item = &str> {
+    between(page, "<article", "</article>")
+}
+
+/// The answers of the examples are emphasized code, whichever way the tags nest.
+fn last_emphasized_code(article: &str) -> Option<&str> {
+    [
+        ("<code><em>", "</em></code>"),
+        ("<em><code>", "</code></em>"),
+    ]
+    .into_iter()
+    .filter_map(|(open, close)| {
+        let start = article.rfind(open)? + open.len();
+        let (code, _) = article[start..].split_once(close)?;
+        Some((start, code))
+    })
+    .max_by_key(|&(start, _)| start)
+    .map(|(_, code)| code)
+}
+
+/// Every part of `text` found between `open` and the next `close`.
+fn between<'a>(text: &'a str, open: &'a str, close: &'a str) -> impl Iterator<Item = &'a str> {
+    let mut rest = text;
+    core::iter::from_fn(move || {
+        let (_, after) = rest.split_once(open)?;
+        let (inside, after) = after.split_once(close)?;
+        rest = after;
+        Some(inside)
+    })
+}
+
+/// Text of an HTML fragment, without markup and with entities decoded.
+fn text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    [
+        ("&lt;", "<"),
+        ("&gt;", ">"),
+        ("&quot;", "\""),
+        ("&#39;", "'"),
+        ("&amp;", "&"),
+    ]
+    .into_iter()
+    .fold(text, |text, (entity, c)| text.replace(entity, c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Test ---</h2>
+<p>For example:</p>
+<pre><code>L68
+R&lt;<em>30</em>
+</code></pre>
+<p>Following these steps, <code>2</code> clicks and the password is <code><em>3</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>1055</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>In the above example, the password would be <em><code>6</code></em>.</p>
+</article>
+</main></body></html>"#;
+
+    #[test]
+    fn extracts_examples_of_each_part() -> miette::Result<()> {
+        let examples = extract(PAGE)?;
+        let input = "L68\nR<30\n".to_owned();
+        assert_eq!(
+            examples,
+            vec![
+                (
+                    1,
+                    Example {
+                        input: input.clone(),
+                        answer: Answer::Number(3),
+                    },
+                ),
+                (
+                    2,
+                    Example {
+                        input,
+                        answer: Answer::Number(6),
+                    },
+                ),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn reports_missing_examples() {
+        assert!(extract("<html></html>").is_err());
+        assert!(extract("<article><code><em>3</em></code></article>").is_err());
+        assert!(extract("<article><pre><code>1\n</code></pre></article>").is_err());
+    }
+}
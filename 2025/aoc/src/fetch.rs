@@ -0,0 +1,25 @@
+use std::path::{Path, PathBuf};
+
+use advent::input::input_path;
+use miette::{Context, IntoDiagnostic};
+
+use crate::client::Client;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Downloads the input of `day` into `day_dir`, unless it is cached there already.
+pub fn fetch_input(client: &Client, year: u16, day: u8, day_dir: &Path) -> miette::Result<Fetched> {
+    let path = input_path(day_dir);
+    if path.exists() {
+        return Ok(Fetched::Cached(path));
+    }
+    let input = client.input(year, day)?;
+    std::fs::write(&path, input)
+        .into_diagnostic()
+        .with_context(|| format!("cache input {}", path.display()))?;
+    Ok(Fetched::Downloaded(path))
+}
@@ -0,0 +1,94 @@
+use std::path::{Path, PathBuf};
+
+use advent::{
+    Answer, DynSolution,
+    input::Source,
+    parse::{Mode, name_source},
+};
+use miette::miette;
+
+pub mod bench;
+pub mod client;
+mod days;
+pub mod examples;
+pub mod fetch;
+pub mod scaffold;
+pub mod submit;
+pub mod verify;
+
+pub use days::DAYS;
+
+/// The year solved by the days in this workspace.
+pub const YEAR: u16 = 2025;
+
+const WORKSPACE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/..");
+
+pub fn workspace_dir() -> &'static Path {
+    Path::new(WORKSPACE_DIR)
+}
+
+/// The known answers of [`YEAR`].
+pub fn answers_path() -> PathBuf {
+    workspace_dir().join(submit::ANSWERS_FILE)
+}
+
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub number: u8,
+    pub parts: [&'static dyn DynSolution; 2],
+}
+
+impl Day {
+    pub const fn new(
+        number: u8,
+        part_1: &'static dyn DynSolution,
+        part_2: &'static dyn DynSolution,
+    ) -> Self {
+        Self {
+            number,
+            parts: [part_1, part_2],
+        }
+    }
+
+    /// The directory of the day crate, which also holds its `input.txt`.
+    pub fn dir(&self) -> PathBuf {
+        workspace_dir().join(format!("day-{:02}", self.number))
+    }
+
+    pub fn part(&self, part: u8) -> miette::Result<&'static dyn DynSolution> {
+        part.checked_sub(1)
+            .and_then(|i| self.parts.get(usize::from(i)))
+            .copied()
+            .ok_or_else(|| miette!("day {:02} has no part {part}", self.number))
+    }
+
+    /// Solves `part` on `input`, pointing parse errors at the `source` it was read from.
+    pub fn solve(
+        &self,
+        part: u8,
+        input: &str,
+        source: &Source,
+        mode: Mode,
+    ) -> miette::Result<Answer> {
+        self.part(part)?
+            .run(input, mode)
+            .map_err(|err| name_source(err, source.to_string()))
+    }
+}
+
+pub fn day(year: u16, number: u8) -> miette::Result<&'static Day> {
+    check_year(year)?;
+    DAYS.iter()
+        .find(|day| day.number == number)
+        .ok_or_else(|| miette!("day {number:02} of {year} is not solved yet"))
+}
+
+pub fn check_year(year: u16) -> miette::Result<()> {
+    if year == YEAR {
+        Ok(())
+    } else {
+        Err(miette!(
+            "only {YEAR} is solved in this workspace, not {year}"
+        ))
+    }
+}
@@ -0,0 +1,359 @@
+use std::{path::PathBuf, time::Duration};
+
+use advent::{Answer, Answers, input::Source, parse::Mode};
+use aoc::{
+    Day,
+    bench::Benchmark,
+    client::Client,
+    fetch::Fetched,
+    verify::{Status, Verification},
+};
+use clap::{Args, Parser, Subcommand};
+use miette::{Context, miette};
+use tracing_subscriber::EnvFilter;
+
+#[derive(Parser)]
+#[command(about = "Advent of Code solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve puzzles on their inputs
+    Run(RunArgs),
+    /// Check every solution against the known answers of its real input
+    Verify(VerifyArgs),
+    /// Time parsing and solving on the real and example inputs, best built with `--release`
+    Bench(BenchArgs),
+    /// Generate the crate of a new day and register it with the runner
+    New(NewArgs),
+    /// Download the input of a day unless it is cached already
+    Fetch(FetchArgs),
+    /// Write the example fixtures of a day from its saved puzzle page
+    Examples(ExamplesArgs),
+    /// Submit the answer to a part, solving it on the real input unless given
+    Submit(SubmitArgs),
+}
+
+#[derive(Args)]
+struct RunArgs {
+    #[arg(long, default_value_t = aoc::YEAR)]
+    year: u16,
+    #[arg(long, required_unless_present = "all")]
+    day: Option<u8>,
+    /// Solve only this part instead of both
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Solve every day
+    #[arg(long, conflicts_with = "day")]
+    all: bool,
+    /// Input file to use instead of the day's `input.txt`, `-` for stdin
+    #[arg(long, conflicts_with = "all")]
+    input: Option<String>,
+    /// Skip input that does not parse instead of failing on it
+    #[arg(long)]
+    lenient: bool,
+}
+
+#[derive(Args)]
+struct VerifyArgs {
+    #[arg(long, default_value_t = aoc::YEAR)]
+    year: u16,
+    /// Verify only this day
+    #[arg(long)]
+    day: Option<u8>,
+    /// Skip input that does not parse instead of failing on it
+    #[arg(long)]
+    lenient: bool,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    #[arg(long, default_value_t = aoc::YEAR)]
+    year: u16,
+    /// Benchmark only this day
+    #[arg(long)]
+    day: Option<u8>,
+    /// Benchmark only this part
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Time spent repeating each part on each input, in milliseconds
+    #[arg(long, default_value_t = 500)]
+    budget: u64,
+}
+
+#[derive(Args)]
+struct NewArgs {
+    #[arg(long, default_value_t = aoc::YEAR)]
+    year: u16,
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+}
+
+#[derive(Args)]
+struct FetchArgs {
+    #[arg(long, default_value_t = aoc::YEAR)]
+    year: u16,
+    #[arg(long)]
+    day: u8,
+    /// Website to download from instead of adventofcode.com, also read from `$AOC_BASE_URL`
+    #[arg(long)]
+    base_url: Option<String>,
+}
+
+#[derive(Args)]
+struct ExamplesArgs {
+    #[arg(long, default_value_t = aoc::YEAR)]
+    year: u16,
+    #[arg(long)]
+    day: u8,
+    /// Puzzle page to read instead of the day's `puzzle.html`
+    #[arg(long)]
+    page: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct SubmitArgs {
+    #[arg(long, default_value_t = aoc::YEAR)]
+    year: u16,
+    #[arg(long)]
+    day: u8,
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+    /// Answer to submit instead of solving the part
+    #[arg(long)]
+    answer: Option<Answer>,
+    /// Website to submit to instead of adventofcode.com, also read from `$AOC_BASE_URL`
+    #[arg(long)]
+    base_url: Option<String>,
+}
+
+fn main() -> miette::Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .init();
+    match Cli::parse().command {
+        Command::Run(args) => run(args),
+        Command::Verify(args) => verify(args),
+        Command::Bench(args) => bench(args),
+        Command::New(args) => new(args),
+        Command::Fetch(args) => fetch(args),
+        Command::Examples(args) => examples(args),
+        Command::Submit(args) => submit(args),
+    }
+}
+
+fn run(args: RunArgs) -> miette::Result<()> {
+    aoc::check_year(args.year)?;
+    let mode = mode(args.lenient);
+    let days = match args.day {
+        Some(day) => vec![aoc::day(args.year, day)?],
+        None => aoc::DAYS.iter().collect(),
+    };
+    let parts = args.part.map_or(vec![1, 2], |part| vec![part]);
+    if let ([day], [part]) = (days.as_slice(), parts.as_slice()) {
+        let source = Source::from_arg(day.dir(), args.input);
+        println!("{}", solve(day, *part, &source.read()?, &source, mode)?);
+        return Ok(());
+    }
+    let mut failed = 0;
+    for day in days {
+        let source = Source::from_arg(day.dir(), args.input.clone());
+        let input = match source.read() {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("{err:?}");
+                failed += parts.len();
+                continue;
+            }
+        };
+        for &part in &parts {
+            match solve(day, part, &input, &source, mode) {
+                Ok(answer) => println!("day {:02} part {part}: {answer}", day.number),
+                Err(err) => {
+                    eprintln!("{err:?}");
+                    failed += 1;
+                }
+            }
+        }
+    }
+    match failed {
+        0 => Ok(()),
+        failed => Err(miette!("{failed} part(s) failed")),
+    }
+}
+
+fn solve(day: &Day, part: u8, input: &str, source: &Source, mode: Mode) -> miette::Result<Answer> {
+    day.solve(part, input, source, mode)
+        .context(format!("solve day {:02} part {part}", day.number))
+}
+
+fn mode(lenient: bool) -> Mode {
+    if lenient { Mode::Lenient } else { Mode::Strict }
+}
+
+fn verify(args: VerifyArgs) -> miette::Result<()> {
+    aoc::check_year(args.year)?;
+    let days = match args.day {
+        Some(day) => vec![aoc::day(args.year, day)?],
+        None => aoc::DAYS.iter().collect(),
+    };
+    let answers = Answers::load(aoc::answers_path())?;
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for verification in aoc::verify::verify(days, &answers, mode(args.lenient)) {
+        let Verification {
+            day,
+            part,
+            status,
+            elapsed,
+        } = verification;
+        let label = format!("day {day:02} part {part}");
+        match status {
+            Status::Pass => {
+                passed += 1;
+                println!("{label}  pass     {elapsed:>10.2?}");
+            }
+            Status::Fail { expected, actual } => {
+                failed += 1;
+                println!("{label}  FAIL     {elapsed:>10.2?}  expected {expected}, got {actual}");
+            }
+            Status::Missing { actual } => {
+                missing += 1;
+                println!("{label}  missing  {elapsed:>10.2?}  got {actual}");
+            }
+            Status::NoInput => {
+                missing += 1;
+                println!("{label}  missing  {:>10}  no input", "-");
+            }
+            Status::Error(err) => {
+                failed += 1;
+                println!("{label}  ERROR    {elapsed:>10.2?}");
+                eprintln!("{err:?}");
+            }
+        }
+    }
+    println!("{passed} passed, {failed} failed, {missing} missing");
+    match failed {
+        0 => Ok(()),
+        failed => Err(miette!("{failed} part(s) failed verification")),
+    }
+}
+
+fn bench(args: BenchArgs) -> miette::Result<()> {
+    aoc::check_year(args.year)?;
+    let days = match args.day {
+        Some(day) => vec![aoc::day(args.year, day)?],
+        None => aoc::DAYS.iter().collect(),
+    };
+    let parts = args.part.map_or(vec![1, 2], |part| vec![part]);
+    let budget = Duration::from_millis(args.budget);
+    let mut benchmarks = Vec::new();
+    for day in days {
+        for &part in &parts {
+            for input in aoc::bench::inputs(day, part) {
+                let (kind, input) = match input {
+                    Ok(input) => input,
+                    Err(err) => {
+                        eprintln!("{err:?}");
+                        continue;
+                    }
+                };
+                eprintln!(
+                    "benchmarking day {:02} part {part} on {kind} input",
+                    day.number
+                );
+                match aoc::bench::bench(day, part, kind, &input, budget) {
+                    Ok(benchmark) => benchmarks.push(benchmark),
+                    Err(err) => eprintln!("{err:?}"),
+                }
+            }
+        }
+    }
+    print_benchmarks(&benchmarks);
+    Ok(())
+}
+
+fn print_benchmarks(benchmarks: &[Benchmark]) {
+    println!(
+        "{:<4} {:<5} {:<8} {:>12} {:>12} {:>12} {:>6}",
+        "day", "part", "input", "parse", "solve", "total", "runs"
+    );
+    for benchmark in benchmarks {
+        println!(
+            "{:<4} {:<5} {:<8} {:>12.2?} {:>12.2?} {:>12.2?} {:>6}",
+            format!("{:02}", benchmark.day),
+            benchmark.part,
+            benchmark.input,
+            benchmark.parse,
+            benchmark.solve,
+            benchmark.total(),
+            benchmark.iterations,
+        );
+    }
+    let [real, example] =
+        [aoc::bench::InputKind::Real, aoc::bench::InputKind::Example].map(|kind| {
+            benchmarks
+                .iter()
+                .filter(|benchmark| benchmark.input == kind)
+                .map(Benchmark::total)
+                .sum::<Duration>()
+        });
+    println!("total on real inputs {real:.2?}, on example inputs {example:.2?}");
+}
+
+fn new(args: NewArgs) -> miette::Result<()> {
+    aoc::check_year(args.year)?;
+    let dir = aoc::scaffold::scaffold(aoc::workspace_dir(), args.day)?;
+    println!("created {}", dir.file_name().unwrap_or_default().display());
+    Ok(())
+}
+
+fn fetch(args: FetchArgs) -> miette::Result<()> {
+    let day = aoc::day(args.year, args.day)?;
+    let client = Client::from_env(args.base_url)?;
+    match aoc::fetch::fetch_input(&client, args.year, day.number, &day.dir())? {
+        Fetched::Cached(path) => println!("cached {}", path.display()),
+        Fetched::Downloaded(path) => println!("downloaded {}", path.display()),
+    }
+    Ok(())
+}
+
+fn examples(args: ExamplesArgs) -> miette::Result<()> {
+    let day = aoc::day(args.year, args.day)?;
+    let page = args
+        .page
+        .unwrap_or_else(|| aoc::examples::puzzle_path(day.dir()));
+    for (part, example) in aoc::examples::extract_into(&page, &day.dir())? {
+        println!(
+            "day {:02} part {part}: {} example lines, answer {}",
+            day.number,
+            example.input.lines().count(),
+            example.answer
+        );
+    }
+    Ok(())
+}
+
+fn submit(args: SubmitArgs) -> miette::Result<()> {
+    let day = aoc::day(args.year, args.day)?;
+    let answer = match args.answer {
+        Some(answer) => answer,
+        None => {
+            let source = Source::default_for(day.dir());
+            solve(day, args.part, &source.read()?, &source, Mode::Strict)?
+        }
+    };
+    let client = Client::from_env(args.base_url)?;
+    let outcome = aoc::submit::submit(
+        &client,
+        aoc::workspace_dir(),
+        args.year,
+        day.number,
+        args.part,
+        &answer,
+    )?;
+    println!("{answer}: {outcome}");
+    Ok(())
+}
@@ -0,0 +1,183 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use miette::{Context, IntoDiagnostic, miette};
+
+const CARGO_TOML: &str = include_str!("../templates/Cargo.toml.template");
+const LIB_RS: &str = include_str!("../templates/lib.rs.template");
+const PART_RS: &str = include_str!("../templates/part.rs.template");
+
+const RUNNER_MANIFEST: &str = "aoc/Cargo.toml";
+const RUNNER_DAYS: &str = "aoc/src/days.rs";
+const DAY_ENTRY: &str = "Day::new(";
+
+/// Generates the crate of `day` in `workspace` and registers it with the runner.
+pub fn scaffold(workspace: &Path, day: u8) -> miette::Result<PathBuf> {
+    let dir = workspace.join(format!("day-{day:02}"));
+    if dir.exists() {
+        return Err(miette!("{} already exists", dir.display()));
+    }
+    fs::create_dir_all(dir.join("src"))
+        .into_diagnostic()
+        .with_context(|| format!("create {}", dir.display()))?;
+    write(&dir.join("Cargo.toml"), render(CARGO_TOML, day, None))?;
+    write(&dir.join("src/lib.rs"), render(LIB_RS, day, None))?;
+    for part in [1, 2] {
+        let path = dir.join(format!("src/part_{part}.rs"));
+        write(&path, render(PART_RS, day, Some(part)))?;
+    }
+    edit(&workspace.join(RUNNER_MANIFEST), |manifest| {
+        register_dependency(manifest, day)
+    })?;
+    edit(&workspace.join(RUNNER_DAYS), |days| register_day(days, day))?;
+    Ok(dir)
+}
+
+/// `template` with its placeholders filled in for `day`, and `part` if it is for one.
+fn render(template: &str, day: u8, part: Option<u8>) -> String {
+    let rendered = template.replace("{{day}}", &format!("{day:02}"));
+    match part {
+        Some(part) => rendered.replace("{{part}}", &part.to_string()),
+        None => rendered,
+    }
+}
+
+fn register_dependency(manifest: &str, day: u8) -> miette::Result<String> {
+    let dependency = format!("day-{day:02} = {{ path = \"../day-{day:02}\" }}\n");
+    let entries = line_starts(manifest)
+        .filter_map(|start| {
+            let line = &manifest[start..];
+            let number = line.strip_prefix("day-")?.get(..2)?.parse::<u8>().ok()?;
+            Some((start, number))
+        })
+        .collect::<Vec<_>>();
+    let at = insertion_point(&entries, day)?.unwrap_or_else(|| {
+        entries
+            .last()
+            .map(|&(start, _)| line_end(manifest, start))
+            .unwrap_or(manifest.len())
+    });
+    Ok(splice(manifest, at, &dependency))
+}
+
+fn register_day(days: &str, day: u8) -> miette::Result<String> {
+    let entry = format!(
+        "    Day::new({day}, &day_{day:02}::part_1::Part1, &day_{day:02}::part_2::Part2),\n"
+    );
+    let entries = days
+        .match_indices(DAY_ENTRY)
+        .filter_map(|(i, _)| {
+            let number = days[i + DAY_ENTRY.len()..]
+                .trim_start()
+                .split(|c: char| !c.is_ascii_digit())
+                .next()?
+                .parse::<u8>()
+                .ok()?;
+            Some((line_start(days, i), number))
+        })
+        .collect::<Vec<_>>();
+    let at = match insertion_point(&entries, day)? {
+        Some(at) => at,
+        None => days
+            .rfind("];")
+            .map(|i| line_start(days, i))
+            .ok_or_else(|| miette!("find the end of the registered days"))?,
+    };
+    Ok(splice(days, at, &entry))
+}
+
+/// Position of the first entry after `day`, keeping the entries sorted.
+fn insertion_point(entries: &[(usize, u8)], day: u8) -> miette::Result<Option<usize>> {
+    if entries.iter().any(|&(_, number)| number == day) {
+        return Err(miette!("day {day:02} is already registered"));
+    }
+    Ok(entries
+        .iter()
+        .find(|&&(_, number)| number > day)
+        .map(|&(start, _)| start))
+}
+
+fn line_starts(text: &str) -> impl Iterator<Item = usize> {
+    core::iter::once(0).chain(text.match_indices('\n').map(|(i, _)| i + 1))
+}
+
+fn line_start(text: &str, i: usize) -> usize {
+    text[..i].rfind('\n').map_or(0, |newline| newline + 1)
+}
+
+fn line_end(text: &str, i: usize) -> usize {
+    text[i..]
+        .find('\n')
+        .map_or(text.len(), |newline| i + newline + 1)
+}
+
+fn splice(text: &str, at: usize, insert: &str) -> String {
+    [&text[..at], insert, &text[at..]].concat()
+}
+
+fn write(path: &Path, contents: String) -> miette::Result<()> {
+    fs::write(path, contents)
+        .into_diagnostic()
+        .with_context(|| format!("write {}", path.display()))
+}
+
+fn edit(path: &Path, f: impl FnOnce(&str) -> miette::Result<String>) -> miette::Result<()> {
+    let text = fs::read_to_string(path)
+        .into_diagnostic()
+        .with_context(|| format!("read {}", path.display()))?;
+    write(
+        path,
+        f(&text).with_context(|| format!("edit {}", path.display()))?,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registers_dependency_in_order() -> miette::Result<()> {
+        let manifest = "\
+[dependencies]
+advent.workspace = true
+day-02 = { path = \"../day-02\" }
+day-04 = { path = \"../day-04\" }
+";
+        let expected = "\
+[dependencies]
+advent.workspace = true
+day-02 = { path = \"../day-02\" }
+day-03 = { path = \"../day-03\" }
+day-04 = { path = \"../day-04\" }
+";
+        assert_eq!(register_dependency(manifest, 3)?, expected);
+        assert!(register_dependency(manifest, 5)?.ends_with("day-05 = { path = \"../day-05\" }\n"));
+        assert!(register_dependency(manifest, 4).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn registers_day_in_order() -> miette::Result<()> {
+        let days = "\
+pub const DAYS: &[Day] = &[
+    Day::new(2, &day_02::part_1::Part1, &day_02::part_2::Part2),
+    Day::new(
+        8,
+        &day_08::part_1::Part1 { connections: 1000 },
+        &day_08::part_2::Part2,
+    ),
+];
+";
+        let registered = register_day(days, 3)?;
+        let third = "    Day::new(3, &day_03::part_1::Part1, &day_03::part_2::Part2),\n";
+        assert!(registered.contains(&format!("Part2),\n{third}    Day::new(\n        8,")));
+        let registered = register_day(days, 12)?;
+        assert!(
+            registered
+                .ends_with("Day::new(12, &day_12::part_1::Part1, &day_12::part_2::Part2),\n];\n")
+        );
+        Ok(())
+    }
+}
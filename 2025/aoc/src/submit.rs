@@ -0,0 +1,195 @@
+use std::{fmt, path::Path, time::Duration};
+
+use advent::{Answer, Answers, WrongAnswers};
+use miette::miette;
+
+use crate::client::Client;
+
+pub const ANSWERS_FILE: &str = "answers.txt";
+pub const WRONG_ANSWERS_FILE: &str = "wrong-answers.txt";
+
+/// What the website made of a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    /// Answered too recently, with the wait left if the page says how long it is.
+    RateLimited(Option<Duration>),
+    AlreadySolved,
+}
+
+/// Submits `answer` unless it is already known, recording the outcome
+/// into the answers kept in `store_dir`.
+pub fn submit(
+    client: &Client,
+    store_dir: &Path,
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: &Answer,
+) -> miette::Result<Outcome> {
+    let answers_path = store_dir.join(ANSWERS_FILE);
+    let wrong_answers_path = store_dir.join(WRONG_ANSWERS_FILE);
+    let mut answers = Answers::load(&answers_path)?;
+    let mut wrong_answers = WrongAnswers::load(&wrong_answers_path)?;
+    match answers.get(day, part) {
+        Some(known) if known == answer => return Ok(Outcome::AlreadySolved),
+        Some(known) => {
+            return Err(miette!(
+                "day {day:02} part {part} is already solved with {known}, not {answer}"
+            ));
+        }
+        None if wrong_answers.contains(day, part, answer) => {
+            return Err(miette!(
+                "{answer} is already known to be wrong for day {day:02} part {part}"
+            ));
+        }
+        None => {}
+    }
+    let page = client.answer(year, day, part, &answer.to_string())?;
+    let outcome = outcome(&page)?;
+    match outcome {
+        Outcome::Correct => {
+            answers.insert(day, part, answer.clone());
+            answers.save(&answers_path)?;
+        }
+        Outcome::TooHigh | Outcome::TooLow | Outcome::Incorrect => {
+            wrong_answers.insert(day, part, answer.clone());
+            wrong_answers.save(&wrong_answers_path)?;
+        }
+        Outcome::RateLimited(_) | Outcome::AlreadySolved => {}
+    }
+    Ok(outcome)
+}
+
+/// Reads the outcome from the page the website responds with to an answer.
+pub fn outcome(page: &str) -> miette::Result<Outcome> {
+    let text = article_text(page);
+    let outcome = if text.contains("That's the right answer") {
+        Outcome::Correct
+    } else if text.contains("You don't seem to be solving the right level") {
+        Outcome::AlreadySolved
+    } else if text.contains("You gave an answer too recently") {
+        Outcome::RateLimited(wait_time(&text))
+    } else if text.contains("That's not the right answer") {
+        if text.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if text.contains("your answer is too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Incorrect
+        }
+    } else {
+        return Err(miette!("unrecognized response: {}", text.trim()));
+    };
+    Ok(outcome)
+}
+
+/// Text of the `<article>` holding the response message, without markup.
+fn article_text(page: &str) -> String {
+    let article = page
+        .split_once("<article")
+        .and_then(|(_, article)| article.split_once('>'))
+        .and_then(|(_, article)| article.split_once("</article>"))
+        .map_or(page, |(article, _)| article);
+    let mut text = String::with_capacity(article.len());
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// The wait in `you have 1m 5s left to wait`.
+fn wait_time(text: &str) -> Option<Duration> {
+    let (before, _) = text.split_once(" left to wait")?;
+    let (_, wait) = before.rsplit_once("have ")?;
+    wait.split_whitespace()
+        .map(|amount| {
+            let value = amount.strip_suffix(['h', 'm', 's'])?.parse::<u64>().ok()?;
+            match amount.chars().last()? {
+                'h' => Some(value * 60 * 60),
+                'm' => Some(value * 60),
+                _ => Some(value),
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::TooHigh => write!(f, "wrong, too high"),
+            Self::TooLow => write!(f, "wrong, too low"),
+            Self::Incorrect => write!(f, "wrong"),
+            Self::RateLimited(Some(wait)) => write!(f, "rate limited, wait {}s", wait.as_secs()),
+            Self::RateLimited(None) => write!(f, "rate limited, wait time unknown"),
+            Self::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(message: &str) -> String {
+        format!("<html><body><main><article><p>{message}</p></article></main></body></html>")
+    }
+
+    #[test]
+    fn reads_outcomes() -> miette::Result<()> {
+        let correct = page(
+            "That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.",
+        );
+        assert_eq!(outcome(&correct)?, Outcome::Correct);
+        let too_high = page(
+            "That's not the right answer; your answer is too high.  If you're stuck, \
+             make sure you're using the full input data.",
+        );
+        assert_eq!(outcome(&too_high)?, Outcome::TooHigh);
+        let too_low = page("That's not the right answer; your answer is too low.");
+        assert_eq!(outcome(&too_low)?, Outcome::TooLow);
+        let wrong = page("That's not the right answer.  If you're stuck, ...");
+        assert_eq!(outcome(&wrong)?, Outcome::Incorrect);
+        let solved = page(
+            "You don't seem to be solving the right level.  Did you already complete it? \
+             <a href=\"/2025/day/8\">[Return to Day 8]</a>",
+        );
+        assert_eq!(outcome(&solved)?, Outcome::AlreadySolved);
+        Ok(())
+    }
+
+    #[test]
+    fn reads_wait_time() -> miette::Result<()> {
+        let limited = page(
+            "You gave an answer too recently; you have to wait after submitting an answer \
+             before trying again.  You have 4m 12s left to wait.",
+        );
+        assert_eq!(
+            outcome(&limited)?,
+            Outcome::RateLimited(Some(Duration::from_secs(252)))
+        );
+        let unknown = page("You gave an answer too recently; please wait a while.");
+        let outcome = outcome(&unknown)?;
+        assert_eq!(outcome, Outcome::RateLimited(None));
+        assert_eq!(outcome.to_string(), "rate limited, wait time unknown");
+        let garbled = page("You gave an answer too recently.  You have 1é left to wait.");
+        assert_eq!(super::outcome(&garbled)?, Outcome::RateLimited(None));
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_unknown_pages() {
+        assert!(outcome(&page("Something else entirely.")).is_err());
+    }
+}
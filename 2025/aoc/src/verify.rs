@@ -0,0 +1,121 @@
+use std::time::{Duration, Instant};
+
+use advent::{Answer, Answers, input::Source, parse::Mode};
+
+use crate::Day;
+
+/// Outcome of checking one part against its known answer.
+#[derive(Debug)]
+pub enum Status {
+    Pass,
+    Fail {
+        expected: Answer,
+        actual: Answer,
+    },
+    /// The part was solved, but its answer is not known yet.
+    Missing {
+        actual: Answer,
+    },
+    NoInput,
+    Error(miette::Report),
+}
+
+#[derive(Debug)]
+pub struct Verification {
+    pub day: u8,
+    pub part: u8,
+    pub status: Status,
+    pub elapsed: Duration,
+}
+
+/// Solves every part of `days` on its real input, parsed in `mode`, and compares it to `answers`.
+pub fn verify<'a>(
+    days: impl IntoIterator<Item = &'a Day>,
+    answers: &'a Answers,
+    mode: Mode,
+) -> impl Iterator<Item = Verification> {
+    days.into_iter().flat_map(move |day| {
+        let source = Source::default_for(day.dir());
+        (1..=2).map(move |part| verify_part(day, part, &source, answers, mode))
+    })
+}
+
+/// Solves `part` of `day` on the input in `source`, which has not been fetched
+/// if it does not exist.
+fn verify_part(
+    day: &Day,
+    part: u8,
+    source: &Source,
+    answers: &Answers,
+    mode: Mode,
+) -> Verification {
+    let input = source.read_if_exists();
+    let start = Instant::now();
+    let status = match input {
+        Err(err) => Status::Error(err),
+        Ok(None) => Status::NoInput,
+        Ok(Some(input)) => match day.solve(part, &input, source, mode) {
+            Err(err) => Status::Error(err),
+            Ok(actual) => match answers.get(day.number, part) {
+                None => Status::Missing { actual },
+                Some(expected) if *expected == actual => Status::Pass,
+                Some(expected) => Status::Fail {
+                    expected: expected.clone(),
+                    actual,
+                },
+            },
+        },
+    };
+    Verification {
+        day: day.number,
+        part,
+        status,
+        elapsed: start.elapsed(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use advent::Example;
+
+    use super::*;
+
+    fn status(source: &Source, answers: &Answers) -> Status {
+        let day = crate::day(crate::YEAR, 1).unwrap();
+        verify_part(day, 1, source, answers, Mode::Strict).status
+    }
+
+    #[test]
+    fn classifies_parts() -> miette::Result<()> {
+        let day = crate::day(crate::YEAR, 1)?;
+        let example = Example::load(day.dir(), 1)?;
+        let source = Source::example_for(day.dir(), 1);
+        let mut answers = Answers::default();
+        assert!(matches!(status(&source, &answers), Status::Missing { .. }));
+        answers.insert(1, 1, example.answer.clone());
+        assert!(matches!(status(&source, &answers), Status::Pass));
+        answers.insert(1, 1, Answer::from("not the answer"));
+        assert!(matches!(
+            status(&source, &answers),
+            Status::Fail { expected, actual }
+                if expected == *"not the answer" && actual == example.answer
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn missing_input_is_not_an_error() -> miette::Result<()> {
+        let day = crate::day(crate::YEAR, 1)?;
+        let missing = Source::File(day.dir().join("does-not-exist.txt"));
+        assert!(matches!(
+            status(&missing, &Answers::default()),
+            Status::NoInput
+        ));
+        let unreadable = Source::File(day.dir());
+        assert!(matches!(
+            status(&unreadable, &Answers::default()),
+            Status::Error(_)
+        ));
+        Ok(())
+    }
+}
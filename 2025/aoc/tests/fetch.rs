@@ -0,0 +1,41 @@
+mod common;
+
+use aoc::{
+    client::{Client, USER_AGENT},
+    fetch::{Fetched, fetch_input},
+};
+use common::{Server, temp_dir};
+
+#[test]
+fn downloads_input_once() -> miette::Result<()> {
+    let server = Server::start(|request| match request.path.as_str() {
+        "/2025/day/8/input" => (200, "162,817,812\n57,618,57\n".into()),
+        _ => (404, String::new()),
+    });
+    let client = Client::new(&server.url, "secret");
+    let dir = temp_dir("fetch");
+    let downloaded = fetch_input(&client, 2025, 8, &dir)?;
+    assert_eq!(downloaded, Fetched::Downloaded(dir.join("input.txt")));
+    assert_eq!(
+        std::fs::read_to_string(dir.join("input.txt")).unwrap(),
+        "162,817,812\n57,618,57\n"
+    );
+    let cached = fetch_input(&client, 2025, 8, &dir)?;
+    assert_eq!(cached, Fetched::Cached(dir.join("input.txt")));
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].header("cookie"), Some("session=secret"));
+    assert_eq!(requests[0].header("user-agent"), Some(USER_AGENT));
+    Ok(())
+}
+
+#[test]
+fn reports_failed_download() {
+    let server = Server::start(|_| (400, "Puzzle inputs differ by user.".into()));
+    let client = Client::new(&server.url, "expired");
+    let dir = temp_dir("fetch-failed");
+    let err = fetch_input(&client, 2025, 1, &dir).unwrap_err();
+    assert!(err.to_string().ends_with("responded with 400 Bad Request"));
+    assert!(!dir.join("input.txt").exists());
+}
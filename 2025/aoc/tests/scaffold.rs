@@ -0,0 +1,53 @@
+use std::{fs, path::Path, process::Command};
+
+/// Workspace the scaffolded crate is checked in, depending on this workspace's
+/// `advent` and pinned to its lock file so that it builds offline.
+fn workspace(dir: &Path) {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let manifest = fs::read_to_string(root.join("Cargo.toml")).unwrap();
+    let dependencies = manifest
+        .split_once("[workspace.dependencies]")
+        .expect("the workspace to declare its dependencies")
+        .1
+        .replace(
+            "advent = { path = \"advent\" }",
+            &format!("advent = {{ path = {:?} }}", root.join("advent")),
+        );
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir.join("aoc/src")).unwrap();
+    fs::write(
+        dir.join("Cargo.toml"),
+        format!(
+            "[workspace]\nresolver = \"2\"\nmembers = [\"day-*\"]\n\n[workspace.dependencies]{dependencies}"
+        ),
+    )
+    .unwrap();
+    fs::copy(root.join("Cargo.lock"), dir.join("Cargo.lock")).unwrap();
+    fs::write(dir.join("aoc/Cargo.toml"), "[dependencies]\n").unwrap();
+    fs::write(
+        dir.join("aoc/src/days.rs"),
+        "pub const DAYS: &[Day] = &[\n];\n",
+    )
+    .unwrap();
+}
+
+#[test]
+fn scaffolded_day_compiles() -> miette::Result<()> {
+    let tmp = Path::new(env!("CARGO_TARGET_TMPDIR"));
+    let dir = tmp.join("scaffold");
+    workspace(&dir);
+    aoc::scaffold::scaffold(&dir, 3)?;
+    let output = Command::new(env!("CARGO"))
+        .args(["check", "--offline", "--all-targets", "--quiet"])
+        .current_dir(&dir)
+        .env("CARGO_TARGET_DIR", tmp.join("scaffold-target"))
+        .env("RUSTFLAGS", "-D warnings")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(())
+}
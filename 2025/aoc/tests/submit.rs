@@ -0,0 +1,78 @@
+mod common;
+
+use advent::{Answer, Answers, WrongAnswers};
+use aoc::{
+    client::Client,
+    submit::{ANSWERS_FILE, Outcome, WRONG_ANSWERS_FILE, submit},
+};
+use common::{Server, temp_dir};
+
+fn page(message: &str) -> String {
+    format!("<html><body><main><article><p>{message}</p></article></main></body></html>")
+}
+
+#[test]
+fn records_correct_answer() -> miette::Result<()> {
+    let server = Server::start(|_| (200, page("That's the right answer!")));
+    let client = Client::new(&server.url, "secret");
+    let dir = temp_dir("submit-correct");
+    let outcome = submit(&client, &dir, 2025, 8, 2, &Answer::Number(25272))?;
+    assert_eq!(outcome, Outcome::Correct);
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/2025/day/8/answer");
+    assert_eq!(requests[0].header("cookie"), Some("session=secret"));
+    assert_eq!(requests[0].body, "level=2&answer=25272");
+    let answers = Answers::load(dir.join(ANSWERS_FILE))?;
+    assert_eq!(answers.get(8, 2), Some(&Answer::Number(25272)));
+    let again = submit(&client, &dir, 2025, 8, 2, &Answer::Number(25272))?;
+    assert_eq!(again, Outcome::AlreadySolved);
+    assert!(submit(&client, &dir, 2025, 8, 2, &Answer::Number(1)).is_err());
+    assert_eq!(server.requests().len(), 1);
+    Ok(())
+}
+
+#[test]
+fn refuses_to_resubmit_wrong_answer() -> miette::Result<()> {
+    let server = Server::start(|_| {
+        (
+            200,
+            page("That's not the right answer; your answer is too low."),
+        )
+    });
+    let client = Client::new(&server.url, "secret");
+    let dir = temp_dir("submit-wrong");
+    let outcome = submit(&client, &dir, 2025, 1, 1, &Answer::Number(3))?;
+    assert_eq!(outcome, Outcome::TooLow);
+    let wrong = WrongAnswers::load(dir.join(WRONG_ANSWERS_FILE))?;
+    assert!(wrong.contains(1, 1, &Answer::Number(3)));
+    let err = submit(&client, &dir, 2025, 1, 1, &Answer::Number(3)).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "3 is already known to be wrong for day 01 part 1"
+    );
+    assert_eq!(server.requests().len(), 1);
+    assert_eq!(Answers::load(dir.join(ANSWERS_FILE))?.get(1, 1), None);
+    Ok(())
+}
+
+#[test]
+fn keeps_nothing_when_rate_limited() -> miette::Result<()> {
+    let server = Server::start(|_| {
+        (
+            200,
+            page("You gave an answer too recently; you have to wait. You have 31s left to wait."),
+        )
+    });
+    let client = Client::new(&server.url, "secret");
+    let dir = temp_dir("submit-limited");
+    let outcome = submit(&client, &dir, 2025, 1, 2, &Answer::Number(6))?;
+    assert_eq!(
+        outcome,
+        Outcome::RateLimited(Some(std::time::Duration::from_secs(31)))
+    );
+    assert!(!dir.join(ANSWERS_FILE).exists());
+    assert!(!dir.join(WRONG_ANSWERS_FILE).exists());
+    Ok(())
+}
@@ -1,23 +1,36 @@
+use advent::{
+    Solution,
+    parse::{Mode, parse_each},
+};
 use nom::{
     IResult, Parser, branch::alt, bytes::complete::tag, character::complete, sequence::pair,
 };
 
-#[tracing::instrument(skip(input), err)]
-pub fn solve(input: &str) -> miette::Result<String> {
-    let zeroes = rotations(input)
-        .scan(50, |dial, rotation| {
-            *dial = (*dial + rotation).rem_euclid(100);
-            Some(*dial)
-        })
-        .filter(|dial| *dial == 0)
-        .count();
-    Ok(zeroes.to_string())
-}
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Part1;
+
+impl Solution for Part1 {
+    type Input = Vec<i32>;
+    type Output = String;
 
-fn rotations(input: &str) -> impl Iterator<Item = i32> {
-    input
-        .lines()
-        .filter_map(|line| rotation.parse(line).map(|(_, rotation)| rotation).ok())
+    #[tracing::instrument(skip(input), err)]
+    fn parse(&self, input: &str, mode: Mode) -> miette::Result<Self::Input> {
+        let rotations = parse_each(rotation, input, input.lines(), mode, "parse rotations")?;
+        Ok(rotations)
+    }
+
+    #[tracing::instrument(skip(input), err)]
+    fn solve(&self, input: &Self::Input) -> miette::Result<Self::Output> {
+        let zeroes = input
+            .iter()
+            .scan(50, |dial, rotation| {
+                *dial = (*dial + rotation).rem_euclid(100);
+                Some(*dial)
+            })
+            .filter(|dial| *dial == 0)
+            .count();
+        Ok(zeroes.to_string())
+    }
 }
 
 fn rotation(input: &str) -> IResult<&str, i32> {
@@ -32,25 +45,15 @@ fn rotation(input: &str) -> IResult<&str, i32> {
 
 #[cfg(test)]
 mod tests {
+    use advent::{Answer, Example};
+
     use super::*;
 
     #[test_log::test]
     fn example_works() -> miette::Result<()> {
-        let input = "\
-L68
-L30
-R48
-L5
-R60
-L55
-L1
-L99
-R14
-L82
-";
-        let expected = "3";
-        let solution = solve(input)?;
-        assert_eq!(solution, expected);
+        let example = Example::load(env!("CARGO_MANIFEST_DIR"), 1)?;
+        let solution = Part1.run(&example.input)?;
+        assert_eq!(Answer::from(solution), example.answer);
         Ok(())
     }
 }
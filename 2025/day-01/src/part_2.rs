@@ -1,27 +1,40 @@
+use advent::{
+    Solution,
+    parse::{Mode, parse_each},
+};
 use nom::{
     IResult, Parser, branch::alt, bytes::complete::tag, character::complete, sequence::pair,
 };
 
-#[tracing::instrument(skip(input), err)]
-pub fn solve(input: &str) -> miette::Result<String> {
-    let zeroes = rotations(input)
-        .scan(50, |dial, rotation| {
-            let overturns = rotation.abs() / 100;
-            let delta = rotation % 100;
-            *dial += delta;
-            let extra_click = *dial != delta && *dial <= 0 || *dial >= 100;
-            *dial = dial.rem_euclid(100);
-            let clicks = overturns + i32::from(extra_click);
-            Some(clicks)
-        })
-        .sum::<i32>();
-    Ok(zeroes.to_string())
-}
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Part2;
+
+impl Solution for Part2 {
+    type Input = Vec<i32>;
+    type Output = String;
 
-fn rotations(input: &str) -> impl Iterator<Item = i32> {
-    input
-        .lines()
-        .filter_map(|line| rotation.parse(line).map(|(_, rotation)| rotation).ok())
+    #[tracing::instrument(skip(input), err)]
+    fn parse(&self, input: &str, mode: Mode) -> miette::Result<Self::Input> {
+        let rotations = parse_each(rotation, input, input.lines(), mode, "parse rotations")?;
+        Ok(rotations)
+    }
+
+    #[tracing::instrument(skip(input), err)]
+    fn solve(&self, input: &Self::Input) -> miette::Result<Self::Output> {
+        let zeroes = input
+            .iter()
+            .scan(50, |dial, rotation| {
+                let overturns = rotation.abs() / 100;
+                let delta = rotation % 100;
+                *dial += delta;
+                let extra_click = *dial != delta && *dial <= 0 || *dial >= 100;
+                *dial = dial.rem_euclid(100);
+                let clicks = overturns + i32::from(extra_click);
+                Some(clicks)
+            })
+            .sum::<i32>();
+        Ok(zeroes.to_string())
+    }
 }
 
 fn rotation(input: &str) -> IResult<&str, i32> {
@@ -36,25 +49,15 @@ fn rotation(input: &str) -> IResult<&str, i32> {
 
 #[cfg(test)]
 mod tests {
+    use advent::{Answer, Example};
+
     use super::*;
 
     #[test_log::test]
     fn example_works() -> miette::Result<()> {
-        let input = "\
-L68
-L30
-R48
-L5
-R60
-L55
-L1
-L99
-R14
-L82
-";
-        let expected = "6";
-        let solution = solve(input)?;
-        assert_eq!(solution, expected);
+        let example = Example::load(env!("CARGO_MANIFEST_DIR"), 2)?;
+        let solution = Part2.run(&example.input)?;
+        assert_eq!(Answer::from(solution), example.answer);
         Ok(())
     }
 }
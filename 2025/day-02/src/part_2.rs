@@ -1,13 +1,40 @@
 use std::ops::RangeInclusive;
 
+use advent::{
+    Solution,
+    parse::{Mode, parse_each},
+};
 use nom::{IResult, Parser, bytes::complete::tag, character::complete, sequence::separated_pair};
 
-#[tracing::instrument(skip(input), err)]
-pub fn solve(input: &str) -> miette::Result<String> {
-    let sum = ids(input)
-        .filter(|id| is_repeating(&id.to_string()))
-        .sum::<u64>();
-    Ok(sum.to_string())
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Part2;
+
+impl Solution for Part2 {
+    type Input = Vec<RangeInclusive<u64>>;
+    type Output = String;
+
+    #[tracing::instrument(skip(input), err)]
+    fn parse(&self, input: &str, mode: Mode) -> miette::Result<Self::Input> {
+        let ranges = parse_each(
+            id_range,
+            input,
+            input.trim_end().split(','),
+            mode,
+            "parse id ranges",
+        )?;
+        Ok(ranges)
+    }
+
+    #[tracing::instrument(skip(input), err)]
+    fn solve(&self, input: &Self::Input) -> miette::Result<Self::Output> {
+        let sum = input
+            .iter()
+            .cloned()
+            .flatten()
+            .filter(|id| is_repeating(&id.to_string()))
+            .sum::<u64>();
+        Ok(sum.to_string())
+    }
 }
 
 fn is_repeating(s: &str) -> bool {
@@ -24,16 +51,6 @@ where
         .is_some_and(|first| it.all(|other| other == first))
 }
 
-fn ids(input: &str) -> impl Iterator<Item = u64> {
-    id_ranges(input).flatten()
-}
-
-fn id_ranges(input: &str) -> impl Iterator<Item = RangeInclusive<u64>> {
-    input
-        .split(',')
-        .filter_map(|range| id_range(range).map(|(_, range)| range).ok())
-}
-
 fn id_range(input: &str) -> IResult<&str, RangeInclusive<u64>> {
     separated_pair(id, tag("-"), id)
         .map(|(start, end)| start..=end)
@@ -46,18 +63,15 @@ fn id(input: &str) -> IResult<&str, u64> {
 
 #[cfg(test)]
 mod tests {
+    use advent::{Answer, Example};
+
     use super::*;
 
     #[test_log::test]
     fn example_works() -> miette::Result<()> {
-        let input = "\
-11-22,95-115,998-1012,1188511880-1188511890,222220-222224,\
-1698522-1698528,446443-446449,38593856-38593862,565653-565659,\
-824824821-824824827,2121212118-2121212124\
-";
-        let expected = "4174379265";
-        let solution = solve(input)?;
-        assert_eq!(solution, expected);
+        let example = Example::load(env!("CARGO_MANIFEST_DIR"), 2)?;
+        let solution = Part2.run(&example.input)?;
+        assert_eq!(Answer::from(solution), example.answer);
         Ok(())
     }
 }
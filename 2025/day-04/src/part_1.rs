@@ -1,82 +1,85 @@
-use miette::{Context, miette};
-use nom::{
-    IResult, Parser,
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::line_ending,
-    multi::{many1, separated_list1},
+use advent::{
+    Solution,
+    grid::{Cell, Grid, Position, grid},
+    parse::{Mode, parse_all},
 };
 
-#[tracing::instrument(skip(input), err)]
-pub fn solve(input: &str) -> miette::Result<String> {
-    let (_, grid) = item_grid
-        .parse(input)
-        .map_err(|err| miette!("{err}"))
-        .context("parse item grid")?;
-    Ok(accessible_rolls(&grid).to_string())
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Part1;
+
+impl Solution for Part1 {
+    type Input = Grid<Item>;
+    type Output = String;
+
+    #[tracing::instrument(skip(input), err)]
+    fn parse(&self, input: &str, mode: Mode) -> miette::Result<Self::Input> {
+        let grid = parse_all(grid, input, mode, "parse item grid")?;
+        Ok(grid)
+    }
+
+    #[tracing::instrument(skip(input), err)]
+    fn solve(&self, input: &Self::Input) -> miette::Result<Self::Output> {
+        Ok(accessible_rolls(input).count().to_string())
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum Item {
+pub enum Item {
     Paper,
     None,
 }
 
-fn accessible_rolls(grid: &[Vec<Item>]) -> usize {
+fn accessible_rolls(grid: &Grid<Item>) -> impl Iterator<Item = Position> {
     grid.iter()
-        .enumerate()
-        .flat_map(|(i, row)| row.iter().enumerate().map(move |(j, item)| (i, j, item)))
-        .filter(|(_, _, item)| **item == Item::Paper)
-        .filter(|(i, j, _)| {
-            (i.checked_sub(1).unwrap_or(*i)..=i + 1)
-                .flat_map(|i| (j.checked_sub(1).unwrap_or(*j)..=j + 1).map(move |j| (i, j)))
-                .filter(|(adj_i, adj_j)| adj_i != i || adj_j != j)
-                .filter_map(|(i, j)| grid.get(i).and_then(|row| row.get(j)))
-                .filter(|adjacent| **adjacent == Item::Paper)
+        .filter(|(_, item)| **item == Item::Paper)
+        .filter(|&(position, _)| {
+            grid.neighbors_8(position)
+                .filter(|&adjacent| grid[adjacent] == Item::Paper)
                 .count()
                 < 4
         })
-        .count()
-}
-
-fn item_grid(input: &str) -> IResult<&str, Vec<Vec<Item>>> {
-    separated_list1(line_ending, item_row).parse(input)
+        .map(|(position, _)| position)
 }
 
-fn item_row(input: &str) -> IResult<&str, Vec<Item>> {
-    many1(item).parse(input)
-}
+impl Cell for Item {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '@' => Some(Self::Paper),
+            '.' => Some(Self::None),
+            _ => None,
+        }
+    }
 
-fn item(input: &str) -> IResult<&str, Item> {
-    alt((tag("."), tag("@")))
-        .map(|item| match item {
-            "@" => Item::Paper,
-            "." => Item::None,
-            _ => unreachable!(),
-        })
-        .parse(input)
+    fn to_char(&self) -> char {
+        match self {
+            Self::Paper => '@',
+            Self::None => '.',
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use advent::{Answer, Example, parse::ParseError};
+
     use super::*;
 
     #[test_log::test]
     fn example_works() -> miette::Result<()> {
-        let input = "\
-..@@.@@@@.
-@@@.@.@.@@
-@@@@@.@.@@
-@.@@@@..@.
-@@.@@@@.@@
-.@@@@@@@.@
-.@.@.@.@@@
-@.@@@.@@@@
-.@@@@@@@@.
-@.@.@@@.@.";
-        let expected = "13";
-        let solution = solve(input)?;
-        assert_eq!(solution, expected);
+        let example = Example::load(env!("CARGO_MANIFEST_DIR"), 1)?;
+        let solution = Part1.run(&example.input)?;
+        assert_eq!(Answer::from(solution), example.answer);
+        Ok(())
+    }
+
+    #[test_log::test]
+    fn reports_only_the_ragged_row() -> miette::Result<()> {
+        let input = "@@.\n@.@\n@.\n..@\n";
+        let err = Part1.run(input).unwrap_err();
+        let err = err.downcast_ref::<ParseError>().expect("a parse error");
+        assert_eq!(err.spans().collect::<Vec<_>>(), vec![8..10]);
+        let grid = Part1.parse(input, Mode::Lenient)?;
+        assert_eq!(grid.to_string(), "@@.\n@.@\n..@");
         Ok(())
     }
 }
@@ -1,38 +1,57 @@
 use std::ops::RangeInclusive;
 
-use miette::{Context, miette};
-use nom::{
-    IResult, Parser,
-    bytes::complete::tag,
-    character::complete::{self, line_ending},
-    multi::many0,
-    sequence::{separated_pair, terminated},
+use advent::{
+    Solution,
+    interval::IntervalSet,
+    parse::{Mode, parse_each},
 };
+use nom::{IResult, Parser, bytes::complete::tag, character::complete, sequence::separated_pair};
 
-#[tracing::instrument(skip(input), err)]
-pub fn solve(input: &str) -> miette::Result<String> {
-    let (input, fresh_ranges) = fresh_ingredients
-        .parse(input)
-        .map_err(|err| miette!("{err}"))
-        .context("parse ingredients")?;
-    let available = available_ingredients(input);
-    let fresh = available
-        .filter(|ingredient| fresh_ranges.iter().any(|range| range.contains(ingredient)))
-        .count();
-    Ok(fresh.to_string())
-}
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Part1;
+
+impl Solution for Part1 {
+    type Input = Ingredients;
+    type Output = String;
+
+    #[tracing::instrument(skip(input), err)]
+    fn parse(&self, input: &str, mode: Mode) -> miette::Result<Self::Input> {
+        let mut lines = input.lines();
+        let fresh_ranges = parse_each(
+            ingredient_range,
+            input,
+            lines.by_ref().take_while(|line| !line.is_empty()),
+            mode,
+            "parse fresh ingredients",
+        )?;
+        let available = parse_each(
+            ingredient,
+            input,
+            lines,
+            mode,
+            "parse available ingredients",
+        )?;
+        Ok(Ingredients {
+            fresh_ranges: fresh_ranges.into_iter().collect(),
+            available,
+        })
+    }
 
-fn fresh_ingredients(input: &str) -> IResult<&str, Vec<RangeInclusive<u64>>> {
-    many0(terminated(ingredient_range, line_ending)).parse(input)
+    #[tracing::instrument(skip(input), err)]
+    fn solve(&self, input: &Self::Input) -> miette::Result<Self::Output> {
+        let fresh = input
+            .available
+            .iter()
+            .filter(|&&ingredient| input.fresh_ranges.contains(ingredient))
+            .count();
+        Ok(fresh.to_string())
+    }
 }
 
-fn available_ingredients(input: &str) -> impl Iterator<Item = u64> {
-    input.lines().filter_map(|line| {
-        ingredient
-            .parse(line)
-            .map(|(_, ingredient)| ingredient)
-            .ok()
-    })
+#[derive(Clone, Debug)]
+pub struct Ingredients {
+    fresh_ranges: IntervalSet,
+    available: Vec<u64>,
 }
 
 fn ingredient_range(input: &str) -> IResult<&str, RangeInclusive<u64>> {
@@ -47,26 +66,25 @@ fn ingredient(input: &str) -> IResult<&str, u64> {
 
 #[cfg(test)]
 mod tests {
+    use advent::{Answer, Example, parse::ParseError};
+
     use super::*;
 
     #[test_log::test]
     fn example_works() -> miette::Result<()> {
-        let input = "\
-3-5
-10-14
-16-20
-12-18
+        let example = Example::load(env!("CARGO_MANIFEST_DIR"), 1)?;
+        let solution = Part1.run(&example.input)?;
+        assert_eq!(Answer::from(solution), example.answer);
+        Ok(())
+    }
 
-1
-5
-8
-11
-17
-32
-";
-        let expected = "3";
-        let solution = solve(input)?;
-        assert_eq!(solution, expected);
+    #[test_log::test]
+    fn reports_each_bad_range() -> miette::Result<()> {
+        let input = "3-5\n1x-2\n10-14\n9-y\n\n1\n5\nz\n11\n";
+        let err = Part1.run(input).unwrap_err();
+        let err = err.downcast_ref::<ParseError>().expect("a parse error");
+        assert_eq!(err.spans().collect::<Vec<_>>(), [5..6, 17..18]);
+        assert_eq!(Part1.run_with(input, Mode::Lenient)?, "2");
         Ok(())
     }
 }
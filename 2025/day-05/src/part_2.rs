@@ -1,45 +1,46 @@
-use std::{cmp::Reverse, ops::RangeInclusive};
+use std::ops::RangeInclusive;
 
-use miette::{Context, miette};
+use advent::{
+    Solution,
+    interval::IntervalSet,
+    parse::{Mode, parse_all},
+};
 use nom::{
     IResult, Parser,
     bytes::complete::tag,
     character::complete::{self, line_ending},
-    multi::many0,
+    multi::{many0, separated_list0},
     sequence::{separated_pair, terminated},
 };
 
-#[tracing::instrument(skip(input), err)]
-pub fn solve(input: &str) -> miette::Result<String> {
-    let (_, mut fresh_ranges) = fresh_ingredients
-        .parse(input)
-        .map_err(|err| miette!("{err}"))
-        .context("parse ingredients")?;
-    fresh_ranges.sort_unstable_by_key(|range| Reverse(range.clone().count()));
-    let fresh = fresh_ranges
-        .iter()
-        .enumerate()
-        .flat_map(|(i, range)| {
-            fresh_ranges
-                .iter()
-                .take(i)
-                .fold(range.clone(), |mut range, prev| {
-                    if !range.is_empty() {
-                        let start = match range.clone().next().unwrap() {
-                            start if prev.contains(&start) => prev.clone().next_back().unwrap() + 1,
-                            start => start,
-                        };
-                        let end = match range.clone().next_back().unwrap() {
-                            end if prev.contains(&end) => prev.clone().next().unwrap() - 1,
-                            end => end,
-                        };
-                        range = start..=end;
-                    }
-                    range
-                })
-        })
-        .count();
-    Ok(fresh.to_string())
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Part2;
+
+impl Solution for Part2 {
+    type Input = IntervalSet;
+    type Output = String;
+
+    #[tracing::instrument(skip(input), err)]
+    fn parse(&self, input: &str, mode: Mode) -> miette::Result<Self::Input> {
+        let fresh_ranges = parse_all(database, input, mode, "parse ingredients")?;
+        Ok(fresh_ranges)
+    }
+
+    #[tracing::instrument(skip(input), err)]
+    fn solve(&self, input: &Self::Input) -> miette::Result<Self::Output> {
+        let fresh = input.len();
+        Ok(fresh.to_string())
+    }
+}
+
+/// The available ingredients listed after the fresh ranges do not matter to this part.
+fn database(input: &str) -> IResult<&str, IntervalSet> {
+    terminated(
+        fresh_ingredients,
+        (line_ending, separated_list0(line_ending, ingredient)),
+    )
+    .map(IntervalSet::from_iter)
+    .parse(input)
 }
 
 fn fresh_ingredients(input: &str) -> IResult<&str, Vec<RangeInclusive<u64>>> {
@@ -58,19 +59,15 @@ fn ingredient(input: &str) -> IResult<&str, u64> {
 
 #[cfg(test)]
 mod tests {
+    use advent::{Answer, Example};
+
     use super::*;
 
     #[test_log::test]
     fn example_works() -> miette::Result<()> {
-        let input = "\
-3-5
-10-14
-16-20
-12-18
-";
-        let expected = "14";
-        let solution = solve(input)?;
-        assert_eq!(solution, expected);
+        let example = Example::load(env!("CARGO_MANIFEST_DIR"), 2)?;
+        let solution = Part2.run(&example.input)?;
+        assert_eq!(Answer::from(solution), example.answer);
         Ok(())
     }
 }
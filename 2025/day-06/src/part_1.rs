@@ -1,26 +1,43 @@
 use std::ops::{Add, Mul};
 
-use miette::{Context, miette};
+use advent::{
+    Solution,
+    input::Normalization,
+    parse::{Mode, parse_all},
+};
 use nom::{
     IResult, Parser,
     branch::alt,
     bytes::complete::tag,
     character::complete::{self, line_ending, space0},
     multi::{many1, separated_list1},
-    sequence::{preceded, separated_pair},
+    sequence::{preceded, separated_pair, terminated},
 };
 
-#[tracing::instrument(skip(input), err)]
-pub fn solve(input: &str) -> miette::Result<String> {
-    let (_, (numbers, operations)) = worksheet
-        .parse(input)
-        .map_err(|err| miette!("{err}"))
-        .context("parse worksheet")?;
-    let answers = numbers
-        .into_iter()
-        .zip(operations)
-        .map(|(operands, operation)| operands.into_iter().reduce(operation).unwrap_or(0));
-    Ok(answers.sum::<u64>().to_string())
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Part1;
+
+impl Solution for Part1 {
+    type Input = (Vec<Vec<u64>>, Vec<Operation>);
+    type Output = String;
+
+    const NORMALIZATION: Normalization = Normalization::KeepWhitespace;
+
+    #[tracing::instrument(skip(input), err)]
+    fn parse(&self, input: &str, mode: Mode) -> miette::Result<Self::Input> {
+        let worksheet = parse_all(worksheet, input, mode, "parse worksheet")?;
+        Ok(worksheet)
+    }
+
+    #[tracing::instrument(skip(input), err)]
+    fn solve(&self, input: &Self::Input) -> miette::Result<Self::Output> {
+        let (numbers, operations) = input;
+        let answers = numbers
+            .iter()
+            .zip(operations)
+            .map(|(operands, operation)| operands.iter().copied().reduce(operation).unwrap_or(0));
+        Ok(answers.sum::<u64>().to_string())
+    }
 }
 
 type Operation = fn(u64, u64) -> u64;
@@ -46,11 +63,11 @@ fn number_lists(input: &str) -> IResult<&str, Vec<Vec<u64>>> {
 }
 
 fn number_row(input: &str) -> IResult<&str, Vec<u64>> {
-    many1(preceded(space0, number)).parse(input)
+    terminated(many1(preceded(space0, number)), space0).parse(input)
 }
 
 fn operation_row(input: &str) -> IResult<&str, Vec<Operation>> {
-    many1(preceded(space0, operation)).parse(input)
+    terminated(many1(preceded(space0, operation)), space0).parse(input)
 }
 
 fn number(input: &str) -> IResult<&str, u64> {
@@ -69,19 +86,33 @@ fn operation(input: &str) -> IResult<&str, Operation> {
 
 #[cfg(test)]
 mod tests {
+    use advent::{Answer, Example};
+
     use super::*;
 
     #[test_log::test]
     fn example_works() -> miette::Result<()> {
-        let input = "\
-123 328  51 64
- 45 64  387 23
-  6 98  215 314
-*   +   *   +  \
-";
-        let expected = "4277556";
-        let solution = solve(input)?;
-        assert_eq!(solution, expected);
+        let example = Example::load(env!("CARGO_MANIFEST_DIR"), 1)?;
+        let solution = Part1.run(&example.input)?;
+        assert_eq!(Answer::from(solution), example.answer);
+        Ok(())
+    }
+
+    #[test_log::test]
+    fn rows_may_be_ragged() -> miette::Result<()> {
+        let example = Example::load(env!("CARGO_MANIFEST_DIR"), 1)?;
+        let width = example.input.lines().map(str::len).max().unwrap_or(0);
+        let trimmed = example.input.lines().map(str::trim_end);
+        let padded = trimmed.clone().map(|row| format!("{row:width$}\n"));
+        let trimmed = trimmed.map(|row| format!("{row}\n"));
+        assert_eq!(
+            Part1.run(&trimmed.collect::<String>())?,
+            Part1.run(&example.input)?
+        );
+        assert_eq!(
+            Part1.run(&padded.collect::<String>())?,
+            Part1.run(&example.input)?
+        );
         Ok(())
     }
 }
@@ -1,26 +1,43 @@
 use std::ops::{Add, Mul};
 
-use miette::{Context, miette};
+use advent::{
+    Solution,
+    input::Normalization,
+    parse::{Mode, parse_all},
+};
 use nom::{
     IResult, Parser,
     branch::alt,
     bytes::{complete::tag, take},
     character::complete::{self, digit1, line_ending, space0, space1},
     multi::{many1, separated_list1},
-    sequence::{preceded, separated_pair},
+    sequence::{preceded, separated_pair, terminated},
 };
 
-#[tracing::instrument(skip(input), err)]
-pub fn solve(input: &str) -> miette::Result<String> {
-    let (_, (numbers, operations)) = worksheet
-        .parse(input)
-        .map_err(|err| miette!("{err}"))
-        .context("parse worksheet")?;
-    let answers = numbers
-        .into_iter()
-        .zip(operations)
-        .map(|(operands, operation)| operands.into_iter().reduce(operation).unwrap_or(0));
-    Ok(answers.sum::<u64>().to_string())
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Part2;
+
+impl Solution for Part2 {
+    type Input = (Vec<Vec<u64>>, Vec<Operation>);
+    type Output = String;
+
+    const NORMALIZATION: Normalization = Normalization::KeepWhitespace;
+
+    #[tracing::instrument(skip(input), err)]
+    fn parse(&self, input: &str, mode: Mode) -> miette::Result<Self::Input> {
+        let worksheet = parse_all(worksheet, input, mode, "parse worksheet")?;
+        Ok(worksheet)
+    }
+
+    #[tracing::instrument(skip(input), err)]
+    fn solve(&self, input: &Self::Input) -> miette::Result<Self::Output> {
+        let (numbers, operations) = input;
+        let answers = numbers
+            .iter()
+            .zip(operations)
+            .map(|(operands, operation)| operands.iter().copied().reduce(operation).unwrap_or(0));
+        Ok(answers.sum::<u64>().to_string())
+    }
 }
 
 type Operation = fn(u64, u64) -> u64;
@@ -77,7 +94,7 @@ fn digit_row(input: &str) -> IResult<&str, Vec<Option<u8>>> {
 }
 
 fn operation_row(input: &str) -> IResult<&str, Vec<Operation>> {
-    many1(preceded(space0, operation)).parse(input)
+    terminated(many1(preceded(space0, operation)), space0).parse(input)
 }
 
 fn maybe_digit(input: &str) -> IResult<&str, Option<u8>> {
@@ -103,20 +120,15 @@ fn operation(input: &str) -> IResult<&str, Operation> {
 
 #[cfg(test)]
 mod tests {
+    use advent::{Answer, Example};
+
     use super::*;
 
     #[test_log::test]
     fn example_works() -> miette::Result<()> {
-        let input = [
-            "123 328  51 64 ",
-            " 45 64  387 23 ",
-            "  6 98  215 314",
-            "*   +   *   +  ",
-        ]
-        .join("\n");
-        let expected = "3263827";
-        let solution = solve(&input)?;
-        assert_eq!(solution, expected);
+        let example = Example::load(env!("CARGO_MANIFEST_DIR"), 2)?;
+        let solution = Part2.run(&example.input)?;
+        assert_eq!(Answer::from(solution), example.answer);
         Ok(())
     }
 }
@@ -1,113 +1,106 @@
 use core::iter::once;
 
-use miette::{Context, miette};
-use nom::{
-    IResult, Parser,
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::line_ending,
-    multi::{many1, separated_list1},
-    sequence::separated_pair,
+use advent::{
+    Solution,
+    grid::{Cell, Grid, grid},
+    parse::{Mode, parse_all},
 };
 
-#[tracing::instrument(skip(input), err)]
-pub fn solve(input: &str) -> miette::Result<u64> {
-    let (_, (beam_row, splitter_field)) = manifold
-        .parse(input)
-        .map_err(|err| miette!("{err}"))
-        .context("parse manifold")?;
-    let splitter_hits = splitter_field
-        .into_iter()
-        .scan(beam_row, |beams, splitters| {
-            let interactions = beams.iter().zip(splitters.iter());
-            let passed_beams = interactions
-                .clone()
-                .map(|(beam, splitter)| beam.filter(|_| splitter.is_none()));
-            let split_beams = interactions
-                .clone()
-                .map(|(beam, splitter)| beam.filter(|_| splitter.is_some()));
-            let split_left = split_beams
-                .clone()
-                .skip(1)
-                .chain(once(None))
-                .take(beams.len());
-            let split_right = once(None).chain(split_beams.clone()).take(beams.len());
-            let splitter_hits = split_beams.flatten().count() as u64;
-            *beams = passed_beams
-                .zip(split_left.zip(split_right))
-                .map(|(pass, (l, r))| pass.or(l).or(r))
-                .collect();
-            Some(splitter_hits)
-        })
-        .sum::<u64>();
-    Ok(splitter_hits)
-}
-
-#[derive(Clone, Copy, Debug)]
-struct Beam;
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Part1;
 
-#[derive(Clone, Copy, Debug)]
-struct Splitter;
+impl Solution for Part1 {
+    type Input = Grid<Tile>;
+    type Output = u64;
 
-type BeamRow = Vec<Option<Beam>>;
-type SplitterRow = Vec<Option<Splitter>>;
-type SplitterField = Vec<SplitterRow>;
-
-fn manifold(input: &str) -> IResult<&str, (BeamRow, SplitterField)> {
-    separated_pair(source_row, line_ending, splitter_field).parse(input)
-}
+    #[tracing::instrument(skip(input), err)]
+    fn parse(&self, input: &str, mode: Mode) -> miette::Result<Self::Input> {
+        let manifold = parse_all(grid, input, mode, "parse manifold")?;
+        Ok(manifold)
+    }
 
-fn source_row(input: &str) -> IResult<&str, BeamRow> {
-    many1(alt((empty_cell.map(|_| None), beam.map(Some)))).parse(input)
+    #[tracing::instrument(skip(input), err)]
+    fn solve(&self, input: &Self::Input) -> miette::Result<Self::Output> {
+        let mut rows = input.rows();
+        let beam_row = rows
+            .next()
+            .map(|row| {
+                row.iter()
+                    .map(|tile| (*tile == Tile::Source).then_some(Beam))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let splitter_hits = rows
+            .scan(beam_row, |beams, tiles| {
+                let interactions = beams.iter().zip(tiles.iter().map(is_splitter));
+                let passed_beams = interactions
+                    .clone()
+                    .map(|(beam, splitter)| beam.filter(|_| !splitter));
+                let split_beams = interactions
+                    .clone()
+                    .map(|(beam, splitter)| beam.filter(|_| splitter));
+                let split_left = split_beams
+                    .clone()
+                    .skip(1)
+                    .chain(once(None))
+                    .take(beams.len());
+                let split_right = once(None).chain(split_beams.clone()).take(beams.len());
+                let splitter_hits = split_beams.flatten().count() as u64;
+                *beams = passed_beams
+                    .zip(split_left.zip(split_right))
+                    .map(|(pass, (l, r))| pass.or(l).or(r))
+                    .collect();
+                Some(splitter_hits)
+            })
+            .sum::<u64>();
+        Ok(splitter_hits)
+    }
 }
 
-fn splitter_field(input: &str) -> IResult<&str, SplitterField> {
-    separated_list1(line_ending, splitter_row).parse(input)
-}
+#[derive(Clone, Copy, Debug)]
+pub struct Beam;
 
-fn splitter_row(input: &str) -> IResult<&str, SplitterRow> {
-    many1(alt((empty_cell.map(|_| None), splitter.map(Some)))).parse(input)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tile {
+    Empty,
+    Source,
+    Splitter,
 }
 
-fn empty_cell(input: &str) -> IResult<&str, &str> {
-    tag(".").parse(input)
+fn is_splitter(tile: &Tile) -> bool {
+    *tile == Tile::Splitter
 }
 
-fn beam(input: &str) -> IResult<&str, Beam> {
-    tag("S").map(|_| Beam).parse(input)
-}
+impl Cell for Tile {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Self::Empty),
+            'S' => Some(Self::Source),
+            '^' => Some(Self::Splitter),
+            _ => None,
+        }
+    }
 
-fn splitter(input: &str) -> IResult<&str, Splitter> {
-    tag("^").map(|_| Splitter).parse(input)
+    fn to_char(&self) -> char {
+        match self {
+            Self::Empty => '.',
+            Self::Source => 'S',
+            Self::Splitter => '^',
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use advent::{Answer, Example};
+
     use super::*;
 
     #[test_log::test]
     fn example_works() -> miette::Result<()> {
-        let input = "\
-.......S.......
-...............
-.......^.......
-...............
-......^.^......
-...............
-.....^.^.^.....
-...............
-....^.^...^....
-...............
-...^.^...^.^...
-...............
-..^...^.....^..
-...............
-.^.^.^.^.^...^.
-...............\
-";
-        let expected = 21;
-        let solution = solve(input)?;
-        assert_eq!(solution, expected);
+        let example = Example::load(env!("CARGO_MANIFEST_DIR"), 1)?;
+        let solution = Part1.run(&example.input)?;
+        assert_eq!(Answer::from(solution), example.answer);
         Ok(())
     }
 }
@@ -1,35 +1,43 @@
 use core::iter::once;
 
-use miette::{Context, miette};
-use nom::{
-    IResult, Parser,
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::line_ending,
-    multi::{many1, separated_list1},
-    sequence::separated_pair,
+use advent::{
+    Solution,
+    grid::{Cell, Grid, grid},
+    parse::{Mode, parse_all},
 };
 
-#[tracing::instrument(skip(input), err)]
-pub fn solve(input: &str) -> miette::Result<u64> {
-    let (_, (beam_row, splitter_field)) = manifold
-        .parse(input)
-        .map_err(|err| miette!("{err}"))
-        .context("parse manifold")?;
-    let timelines = beam_row
-        .into_iter()
-        .map(|beam| if beam.is_some() { 1 } else { 0 })
-        .collect::<Vec<_>>();
-    let timelines = splitter_field
-        .into_iter()
-        .fold(timelines, |timelines, splitters| {
-            let interactions = timelines.iter().copied().zip(splitters.iter());
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Part2;
+
+impl Solution for Part2 {
+    type Input = Grid<Tile>;
+    type Output = u64;
+
+    #[tracing::instrument(skip(input), err)]
+    fn parse(&self, input: &str, mode: Mode) -> miette::Result<Self::Input> {
+        let manifold = parse_all(grid, input, mode, "parse manifold")?;
+        Ok(manifold)
+    }
+
+    #[tracing::instrument(skip(input), err)]
+    fn solve(&self, input: &Self::Input) -> miette::Result<Self::Output> {
+        let mut rows = input.rows();
+        let timelines = rows
+            .next()
+            .map(|row| {
+                row.iter()
+                    .map(|tile| if *tile == Tile::Source { 1 } else { 0 })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let timelines = rows.fold(timelines, |timelines, tiles| {
+            let interactions = timelines.iter().copied().zip(tiles.iter().map(is_splitter));
             let passed_beams = interactions
                 .clone()
-                .map(|(timelines, splitter)| if splitter.is_none() { timelines } else { 0 });
+                .map(|(timelines, splitter)| if splitter { 0 } else { timelines });
             let split_beams = interactions
                 .clone()
-                .map(|(timelines, splitter)| if splitter.is_some() { timelines } else { 0 });
+                .map(|(timelines, splitter)| if splitter { timelines } else { 0 });
             let split_left = split_beams
                 .clone()
                 .skip(1)
@@ -41,75 +49,52 @@ pub fn solve(input: &str) -> miette::Result<u64> {
                 .map(|(pass, (l, r))| pass + l + r)
                 .collect()
         });
-    let total_timelines = timelines.iter().sum::<u64>();
-    Ok(total_timelines)
-}
-
-#[derive(Clone, Copy, Debug)]
-struct Beam;
-
-#[derive(Clone, Copy, Debug)]
-struct Splitter;
-
-type BeamRow = Vec<Option<Beam>>;
-type SplitterRow = Vec<Option<Splitter>>;
-type SplitterField = Vec<SplitterRow>;
-
-fn manifold(input: &str) -> IResult<&str, (BeamRow, SplitterField)> {
-    separated_pair(source_row, line_ending, splitter_field).parse(input)
-}
-
-fn source_row(input: &str) -> IResult<&str, BeamRow> {
-    many1(alt((empty_cell.map(|_| None), beam.map(Some)))).parse(input)
-}
-
-fn splitter_field(input: &str) -> IResult<&str, SplitterField> {
-    separated_list1(line_ending, splitter_row).parse(input)
+        let total_timelines = timelines.iter().sum::<u64>();
+        Ok(total_timelines)
+    }
 }
 
-fn splitter_row(input: &str) -> IResult<&str, SplitterRow> {
-    many1(alt((empty_cell.map(|_| None), splitter.map(Some)))).parse(input)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tile {
+    Empty,
+    Source,
+    Splitter,
 }
 
-fn empty_cell(input: &str) -> IResult<&str, &str> {
-    tag(".").parse(input)
+fn is_splitter(tile: &Tile) -> bool {
+    *tile == Tile::Splitter
 }
 
-fn beam(input: &str) -> IResult<&str, Beam> {
-    tag("S").map(|_| Beam).parse(input)
-}
+impl Cell for Tile {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Self::Empty),
+            'S' => Some(Self::Source),
+            '^' => Some(Self::Splitter),
+            _ => None,
+        }
+    }
 
-fn splitter(input: &str) -> IResult<&str, Splitter> {
-    tag("^").map(|_| Splitter).parse(input)
+    fn to_char(&self) -> char {
+        match self {
+            Self::Empty => '.',
+            Self::Source => 'S',
+            Self::Splitter => '^',
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use advent::{Answer, Example};
+
     use super::*;
 
     #[test_log::test]
     fn example_works() -> miette::Result<()> {
-        let input = "\
-.......S.......
-...............
-.......^.......
-...............
-......^.^......
-...............
-.....^.^.^.....
-...............
-....^.^...^....
-...............
-...^.^...^.^...
-...............
-..^...^.....^..
-...............
-.^.^.^.^.^...^.
-...............\
-";
-        let expected = 40;
-        let solution = solve(input)?;
-        assert_eq!(solution, expected);
+        let example = Example::load(env!("CARGO_MANIFEST_DIR"), 2)?;
+        let solution = Part2.run(&example.input)?;
+        assert_eq!(Answer::from(solution), example.answer);
         Ok(())
     }
 }
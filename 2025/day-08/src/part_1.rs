@@ -1,10 +1,11 @@
-use std::{
-    cmp::Ordering,
-    collections::{BinaryHeap, HashMap, HashSet},
-    rc::Rc,
-};
+use std::cmp::Reverse;
 
-use miette::{Context, miette};
+use advent::{
+    Solution,
+    disjoint_set::DisjointSet,
+    geometry::Point3,
+    parse::{Mode, parse_all},
+};
 use nom::{
     IResult, Parser,
     bytes::complete::tag,
@@ -13,70 +14,57 @@ use nom::{
     sequence::separated_pair,
 };
 
+/// Number of connections made on the real puzzle input.
+pub const CONNECTIONS: usize = 1000;
+
 const TAKE_LARGEST: usize = 3;
 
-#[tracing::instrument(skip(input), err)]
-pub fn solve(input: &str, connections: usize) -> miette::Result<u64> {
-    let (_, boxes) = junction_boxes
-        .parse(input)
-        .map_err(|err| miette!("{err}"))
-        .context("parse junction boxes")?;
-    let circuits = boxes
-        .iter()
-        .map(|b| (*b, Rc::new(HashSet::from([*b]))))
-        .collect::<HashMap<_, _>>();
-    let mut pairs = boxes
-        .iter()
-        .enumerate()
-        .flat_map(|(i, from)| boxes.iter().take(i).map(move |to| (from, to)))
-        .collect::<Vec<_>>();
-    pairs.sort_unstable_by(|left, right| {
-        JunctionBox::distance(left.0, left.1)
-            .partial_cmp(&JunctionBox::distance(right.0, right.1))
-            .unwrap_or(Ordering::Equal)
-    });
-    let circuit_lengths = pairs
-        .into_iter()
-        .take(connections)
-        .fold(circuits, |mut circuits, (from, to)| {
-            let circuit_union = circuits
-                .get(from)
-                .unwrap()
-                .union(circuits.get(to).unwrap())
-                .copied();
-            let new_circuit = Rc::new(circuit_union.collect::<HashSet<_>>());
-            new_circuit.iter().for_each(|junction_box| {
-                *circuits.get_mut(junction_box).unwrap() = new_circuit.clone()
-            });
-            circuits
-        })
-        .into_values()
-        .map(|shared_ptr| (Rc::into_raw(shared_ptr.clone()), shared_ptr))
-        .collect::<HashMap<_, _>>()
-        .into_values()
-        .map(|circuit| circuit.len())
-        .collect::<BinaryHeap<_>>()
-        .into_sorted_vec();
-    let largest_circuits = circuit_lengths.into_iter().rev().take(TAKE_LARGEST);
-    Ok(largest_circuits.product::<usize>() as u64)
+#[derive(Clone, Copy, Debug)]
+pub struct Part1 {
+    pub connections: usize,
 }
 
-#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
-struct JunctionBox {
-    x: u32,
-    y: u32,
-    z: u32,
+impl Default for Part1 {
+    fn default() -> Self {
+        Self {
+            connections: CONNECTIONS,
+        }
+    }
 }
 
-impl JunctionBox {
-    fn distance(&self, other: &Self) -> f64 {
-        let x = (f64::from(self.x) - f64::from(other.x)).powi(2);
-        let y = (f64::from(self.y) - f64::from(other.y)).powi(2);
-        let z = (f64::from(self.z) - f64::from(other.z)).powi(2);
-        (x + y + z).sqrt()
+impl Solution for Part1 {
+    type Input = Vec<JunctionBox>;
+    type Output = u64;
+
+    #[tracing::instrument(skip(input), err)]
+    fn parse(&self, input: &str, mode: Mode) -> miette::Result<Self::Input> {
+        let boxes = parse_all(junction_boxes, input, mode, "parse junction boxes")?;
+        Ok(boxes)
+    }
+
+    #[tracing::instrument(skip(input), err)]
+    fn solve(&self, input: &Self::Input) -> miette::Result<Self::Output> {
+        let boxes = input;
+        let mut pairs = (0..boxes.len())
+            .flat_map(|from| (0..from).map(move |to| (from, to)))
+            .collect::<Vec<_>>();
+        pairs.sort_unstable_by_key(|&(from, to)| boxes[from].squared_distance(&boxes[to]));
+        let mut circuits = DisjointSet::new(boxes.len());
+        pairs
+            .into_iter()
+            .take(self.connections)
+            .for_each(|(from, to)| {
+                circuits.union(from, to);
+            });
+        let mut circuit_lengths = circuits.component_sizes().collect::<Vec<_>>();
+        circuit_lengths.sort_unstable_by_key(|&len| Reverse(len));
+        let largest_circuits = circuit_lengths.into_iter().take(TAKE_LARGEST);
+        Ok(largest_circuits.product::<usize>() as u64)
     }
 }
 
+pub type JunctionBox = Point3<u32>;
+
 fn junction_boxes(input: &str) -> IResult<&str, Vec<JunctionBox>> {
     separated_list1(line_ending, junction_box).parse(input)
 }
@@ -87,43 +75,26 @@ fn junction_box(input: &str) -> IResult<&str, JunctionBox> {
         tag(","),
         separated_pair(complete::u32, tag(","), complete::u32),
     )
-    .map(|(x, (y, z))| JunctionBox { x, y, z })
+    .map(|(x, (y, z))| JunctionBox::new(x, y, z))
     .parse(input)
 }
 
 #[cfg(test)]
 mod tests {
+    use advent::{Answer, Example};
+
     use super::*;
 
     const CONNECTIONS: usize = 10;
 
     #[test_log::test]
     fn example_works() -> miette::Result<()> {
-        let input = "\
-162,817,812
-57,618,57
-906,360,560
-592,479,940
-352,342,300
-466,668,158
-542,29,236
-431,825,988
-739,650,466
-52,470,668
-216,146,977
-819,987,18
-117,168,530
-805,96,715
-346,949,466
-970,615,88
-941,993,340
-862,61,35
-984,92,344
-425,690,689\
-";
-        let expected = 40;
-        let solution = solve(input, CONNECTIONS)?;
-        assert_eq!(solution, expected);
+        let example = Example::load(env!("CARGO_MANIFEST_DIR"), 1)?;
+        let solution = Part1 {
+            connections: CONNECTIONS,
+        }
+        .run(&example.input)?;
+        assert_eq!(Answer::from(solution), example.answer);
         Ok(())
     }
 }
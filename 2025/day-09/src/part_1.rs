@@ -1,6 +1,8 @@
-use std::ops::Add;
-
-use miette::{Context, miette};
+use advent::{
+    Solution,
+    geometry::{Point2, Rectangle},
+    parse::{Mode, parse_all},
+};
 use nom::{
     IResult, Parser,
     bytes::complete::tag,
@@ -9,56 +11,66 @@ use nom::{
     sequence::separated_pair,
 };
 
-#[tracing::instrument(skip(input), err)]
-pub fn solve(input: &str) -> miette::Result<u64> {
-    let (_, tiles) = red_tiles(input)
-        .map_err(|err| miette!("{err}"))
-        .context("parse red tiles")?;
-    let largest_area = tiles
-        .iter()
-        .enumerate()
-        .flat_map(|(i, tile)| tiles.iter().take(i).map(move |opposite| (tile, opposite)))
-        .map(|(a, b)| a.x.abs_diff(b.x).add(1) * a.y.abs_diff(b.y).add(1))
-        .max()
-        .unwrap_or(0);
-    Ok(largest_area)
-}
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Part1;
+
+impl Solution for Part1 {
+    type Input = Vec<TileCoordinate>;
+    type Output = u128;
 
-#[derive(Clone, Copy, Debug)]
-struct TileCoordinate {
-    x: u64,
-    y: u64,
+    #[tracing::instrument(skip(input), err)]
+    fn parse(&self, input: &str, mode: Mode) -> miette::Result<Self::Input> {
+        let tiles = parse_all(red_tiles, input, mode, "parse red tiles")?;
+        Ok(tiles)
+    }
+
+    #[tracing::instrument(skip(input), err)]
+    fn solve(&self, input: &Self::Input) -> miette::Result<Self::Output> {
+        let tiles = input;
+        let largest_area = tiles
+            .iter()
+            .enumerate()
+            .flat_map(|(i, tile)| tiles.iter().take(i).map(move |opposite| (tile, opposite)))
+            .map(|(a, b)| Rectangle::new(*a, *b).area())
+            .max()
+            .unwrap_or(0);
+        Ok(largest_area)
+    }
 }
 
+pub type TileCoordinate = Point2<u64>;
+
 fn red_tiles(input: &str) -> IResult<&str, Vec<TileCoordinate>> {
     separated_list1(line_ending, red_tile).parse(input)
 }
 
 fn red_tile(input: &str) -> IResult<&str, TileCoordinate> {
     separated_pair(complete::u64, tag(","), complete::u64)
-        .map(|(x, y)| TileCoordinate { x, y })
+        .map(|(x, y)| TileCoordinate::new(x, y))
         .parse(input)
 }
 
 #[cfg(test)]
 mod tests {
+    use advent::{Answer, Example, parse::ParseError};
+
     use super::*;
 
     #[test_log::test]
     fn example_works() -> miette::Result<()> {
-        let input = "\
-7,1
-11,1
-11,7
-9,7
-9,5
-2,5
-2,3
-7,3\
-";
-        let expected = 50;
-        let solution = solve(input)?;
-        assert_eq!(solution, expected);
+        let example = Example::load(env!("CARGO_MANIFEST_DIR"), 1)?;
+        let solution = Part1.run(&example.input)?;
+        assert_eq!(Answer::from(solution), example.answer);
+        Ok(())
+    }
+
+    #[test_log::test]
+    fn reports_each_bad_tile() -> miette::Result<()> {
+        let input = "7,1\n11;1\n11,7\n9,x\n2,5\n";
+        let err = Part1.run(input).unwrap_err();
+        let err = err.downcast_ref::<ParseError>().expect("a parse error");
+        assert_eq!(err.spans().collect::<Vec<_>>(), [4..8, 14..17]);
+        assert_eq!(Part1.run_with(input, Mode::Lenient)?, 35);
         Ok(())
     }
 }
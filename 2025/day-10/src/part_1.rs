@@ -1,6 +1,9 @@
 use std::{collections::HashMap, convert::identity, iter};
 
-use miette::{Context, miette};
+use advent::{
+    Solution,
+    parse::{Mode, parse_all},
+};
 use nom::{
     IResult, Parser,
     branch::alt,
@@ -11,14 +14,25 @@ use nom::{
 };
 use tap::prelude::*;
 
-#[tracing::instrument(skip(input), err)]
-pub fn solve(input: &str) -> miette::Result<u64> {
-    let (_, machines) = machines
-        .parse(input)
-        .map_err(|err| miette!("{err}"))
-        .context("parse machines")?;
-    let total_presses = machines.iter().map(solve_machine).sum();
-    Ok(total_presses)
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Part1;
+
+impl Solution for Part1 {
+    type Input = Vec<Machine>;
+    type Output = u64;
+
+    #[tracing::instrument(skip(input), err)]
+    fn parse(&self, input: &str, mode: Mode) -> miette::Result<Self::Input> {
+        let machines = parse_all(machines, input, mode, "parse machines")?;
+        Ok(machines)
+    }
+
+    #[tracing::instrument(skip(input), err)]
+    fn solve(&self, input: &Self::Input) -> miette::Result<Self::Output> {
+        let machines = input;
+        let total_presses = machines.iter().map(solve_machine).sum();
+        Ok(total_presses)
+    }
 }
 
 fn solve_machine(machine: &Machine) -> u64 {
@@ -58,13 +72,13 @@ fn solve_machine(machine: &Machine) -> u64 {
 }
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
-enum Light {
+pub enum Light {
     On,
     Off,
 }
 
 #[derive(Clone, Debug)]
-struct Machine {
+pub struct Machine {
     lights: Vec<Light>,
     buttons: Vec<Vec<usize>>,
     #[allow(unused)]
@@ -131,18 +145,15 @@ impl Light {
 
 #[cfg(test)]
 mod tests {
+    use advent::{Answer, Example};
+
     use super::*;
 
     #[test_log::test]
     fn example_works() -> miette::Result<()> {
-        let input = "\
-[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
-[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
-[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}/
-";
-        let expected = 7;
-        let solution = solve(input)?;
-        assert_eq!(solution, expected);
+        let example = Example::load(env!("CARGO_MANIFEST_DIR"), 1)?;
+        let solution = Part1.run(&example.input)?;
+        assert_eq!(Answer::from(solution), example.answer);
         Ok(())
     }
 }
@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+
+use advent::{
+    bitset::BitSet,
+    graph::{CountError, Graph, NodeId, PathCount},
+};
+use miette::{bail, miette};
+use nom::{
+    IResult, Parser,
+    bytes::complete::tag,
+    character::complete::{alpha1, line_ending, space1},
+    multi::separated_list1,
+    sequence::separated_pair,
+};
+
+/// The device every path ends at.
+pub const END: &str = "out";
+
+/// Paths are counted in this type and it is an error for there to be more.
+pub type Count = u128;
+
+const ENDPOINT_STYLE: &str = "shape=doublecircle";
+const CHECKPOINT_STYLE: &str = "style=filled, fillcolor=gold";
+
+/// The paths between two devices that pass a list of checkpoints, to count
+/// and inspect them.
+#[derive(Clone, Debug)]
+pub struct Route<'a> {
+    connections: &'a Graph,
+    start: NodeId,
+    checkpoints: Vec<NodeId>,
+    end: NodeId,
+}
+
+impl<'a> Route<'a> {
+    pub fn new(connections: &'a Graph, start: &str, end: &str) -> miette::Result<Self> {
+        Ok(Self {
+            connections,
+            start: device(connections, start)?,
+            checkpoints: Vec::new(),
+            end: device(connections, end)?,
+        })
+    }
+
+    /// The paths that also pass every one of `checkpoints`, in any order.
+    pub fn through(mut self, checkpoints: &[impl AsRef<str>]) -> miette::Result<Self> {
+        for name in checkpoints {
+            self.checkpoints
+                .push(device(self.connections, name.as_ref())?);
+        }
+        Ok(self)
+    }
+
+    /// Number of paths from the start to the end through every checkpoint.
+    pub fn count(&self) -> miette::Result<Count> {
+        let paths = if self.checkpoints.is_empty() {
+            self.connections.count_paths(self.start, self.end)
+        } else {
+            number_of_paths(self.connections, self.start, &self.checkpoints, self.end)
+        };
+        paths.map_err(|err| count_error(self.connections, err))
+    }
+
+    /// Paths from the start to the end through every checkpoint as device names,
+    /// found as the iterator is advanced.
+    pub fn paths(&self) -> impl Iterator<Item = Vec<&'a str>> + use<'a> {
+        let connections = self.connections;
+        connections
+            .paths_through(self.start, &self.checkpoints, self.end)
+            .map(|path| names(connections, &path))
+    }
+
+    /// The same paths with the fewest devices first, so that taking `k` gives the `k` shortest.
+    pub fn shortest_paths(&self) -> impl Iterator<Item = Vec<&'a str>> + use<'a> {
+        let connections = self.connections;
+        connections
+            .shortest_paths_through(self.start, &self.checkpoints, self.end)
+            .map(|path| names(connections, &path))
+    }
+
+    /// Devices that every path from the start to the end passes through, in the
+    /// order they are passed and both ends included.
+    pub fn mandatory_devices(&self) -> miette::Result<Vec<&'a str>> {
+        let devices = self
+            .connections
+            .mandatory_nodes(self.start, self.end)
+            .ok_or_else(|| self.unreachable())?;
+        Ok(names(self.connections, &devices))
+    }
+
+    /// Devices apart from the start and end without which no path connects them.
+    pub fn articulation_devices(&self) -> miette::Result<Vec<&'a str>> {
+        let devices = self
+            .connections
+            .articulation_nodes(self.start, self.end)
+            .ok_or_else(|| self.unreachable())?;
+        Ok(names(self.connections, &devices))
+    }
+
+    /// The connections in Graphviz DOT, with the start, end and checkpoints highlighted.
+    pub fn dot(&self) -> String {
+        let highlights = [(self.start, ENDPOINT_STYLE), (self.end, ENDPOINT_STYLE)]
+            .into_iter()
+            .chain(
+                self.checkpoints
+                    .iter()
+                    .map(|&device| (device, CHECKPOINT_STYLE)),
+            )
+            .collect::<Vec<_>>();
+        self.connections.to_dot(&highlights)
+    }
+
+    fn unreachable(&self) -> miette::Report {
+        miette!(
+            "no path leads from {} to {}",
+            self.connections.name(self.start),
+            self.connections.name(self.end)
+        )
+    }
+}
+
+/// Checks that every device apart from the end lists its outputs, and that no
+/// device feeds back into itself, so that paths can be counted.
+pub fn validate(connections: &Graph) -> miette::Result<()> {
+    if let Some(cycle) = connections.find_cycle() {
+        let devices = cycle
+            .iter()
+            .chain(cycle.first())
+            .map(|&device| connections.name(device))
+            .collect::<Vec<_>>();
+        bail!("devices are connected in a cycle: {}", devices.join(" -> "));
+    }
+    let undefined = connections
+        .undeclared()
+        .map(|device| connections.name(device))
+        .filter(|&name| name != END)
+        .collect::<Vec<_>>();
+    if !undefined.is_empty() {
+        bail!(
+            "devices {} are used as outputs but never defined",
+            undefined.join(", ")
+        );
+    }
+    Ok(())
+}
+
+pub fn connections(input: &str) -> IResult<&str, Graph> {
+    separated_list1(
+        line_ending,
+        separated_pair(alpha1, tag(": "), separated_list1(space1, alpha1)),
+    )
+    .map(Graph::new)
+    .parse(input)
+}
+
+fn device(connections: &Graph, name: &str) -> miette::Result<NodeId> {
+    connections
+        .id(name)
+        .ok_or_else(|| miette!("no device named {name}"))
+}
+
+fn names<'a>(connections: &'a Graph, path: &[NodeId]) -> Vec<&'a str> {
+    path.iter()
+        .map(|&device| connections.name(device))
+        .collect()
+}
+
+fn count_error(connections: &Graph, err: CountError) -> miette::Report {
+    match err {
+        CountError::Cycle => miette!("devices are connected in a cycle"),
+        CountError::Overflow(device) => miette!(
+            "more paths pass through {} than a {} can count",
+            connections.name(device),
+            core::any::type_name::<Count>()
+        ),
+    }
+}
+
+fn number_of_paths<C: PathCount>(
+    connections: &Graph,
+    start: NodeId,
+    must_pass: &[NodeId],
+    end: NodeId,
+) -> Result<C, CountError> {
+    let mut checkpoints = vec![Vec::new(); connections.len()];
+    must_pass
+        .iter()
+        .enumerate()
+        .for_each(|(i, &device)| checkpoints[device].push(i));
+    let remaining = BitSet::full(must_pass.len());
+    let mut dp = HashMap::new();
+    number_of_paths_dp(&mut dp, connections, &checkpoints, start, &remaining, end)
+}
+
+/// Paths from `start` to `end` through the checkpoints whose indices are in
+/// `remaining`, with the indices of the checkpoints at each device in `checkpoints`.
+///
+/// Counts are memoized by the checkpoints that remain and then by device.
+/// Overflow is reported at the device whose outputs lead to too many paths.
+fn number_of_paths_dp<C: PathCount>(
+    dp: &mut HashMap<BitSet, Vec<Option<C>>>,
+    connections: &Graph,
+    checkpoints: &[Vec<usize>],
+    start: NodeId,
+    remaining: &BitSet,
+    end: NodeId,
+) -> Result<C, CountError> {
+    let passed;
+    let remaining = if checkpoints[start].is_empty() {
+        remaining
+    } else {
+        let mut rest = remaining.clone();
+        checkpoints[start].iter().for_each(|&i| {
+            rest.remove(i);
+        });
+        passed = rest;
+        &passed
+    };
+    if let Some(cached) = dp.get(remaining).and_then(|paths| paths[start].clone()) {
+        return Ok(cached);
+    }
+    let computed = if start == end && remaining.is_empty() {
+        C::one()
+    } else {
+        connections
+            .successors(start)
+            .iter()
+            .try_fold(C::zero(), |paths, &output| {
+                let output_paths =
+                    number_of_paths_dp(dp, connections, checkpoints, output, remaining, end)?;
+                paths
+                    .checked_add(&output_paths)
+                    .ok_or(CountError::Overflow(start))
+            })?
+    };
+    match dp.get_mut(remaining) {
+        Some(paths) => paths[start] = Some(computed.clone()),
+        None => {
+            let mut paths = vec![None; connections.len()];
+            paths[start] = Some(computed.clone());
+            dp.insert(remaining.clone(), paths);
+        }
+    }
+    Ok(computed)
+}
@@ -1,2 +1,3 @@
+pub mod devices;
 pub mod part_1;
 pub mod part_2;
@@ -1,91 +1,119 @@
-use std::collections::HashMap;
-
-use miette::{Context, miette};
-use nom::{
-    IResult, Parser,
-    bytes::complete::tag,
-    character::complete::{alpha1, line_ending, space1},
-    multi::separated_list1,
-    sequence::separated_pair,
+use advent::{
+    Solution,
+    graph::Graph,
+    parse::{Mode, parse_all},
 };
 
-#[tracing::instrument(skip(input), err)]
-pub fn solve(input: &str) -> miette::Result<u32> {
-    let (_, connections) = connections
-        .parse(input)
-        .map_err(|err| miette!("{err}"))
-        .context("parse connections")?;
-    let paths = number_of_paths(&connections, &Device::You, &Device::Out);
-    Ok(paths)
-}
+use crate::devices::{Count, END, Route, connections, validate};
 
-#[derive(Clone, Debug, Hash, PartialEq, Eq)]
-enum Device {
-    You,
-    Out,
-    Other(Box<str>),
-}
+const START: &str = "you";
 
-fn number_of_paths(
-    connections: &HashMap<Device, Vec<Device>>,
-    start: &Device,
-    end: &Device,
-) -> u32 {
-    if start == end {
-        1
-    } else {
-        connections
-            .get(start)
-            .map(|outputs| {
-                outputs
-                    .iter()
-                    .map(|output| number_of_paths(connections, output, end))
-                    .sum()
-            })
-            .unwrap_or(0)
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Part1;
+
+impl Solution for Part1 {
+    type Input = Graph;
+    type Output = Count;
+
+    #[tracing::instrument(skip(input), err)]
+    fn parse(&self, input: &str, mode: Mode) -> miette::Result<Self::Input> {
+        let connections = parse_all(connections, input, mode, "parse connections")?;
+        validate(&connections)?;
+        Ok(connections)
     }
-}
 
-fn connections(input: &str) -> IResult<&str, HashMap<Device, Vec<Device>>> {
-    separated_list1(
-        line_ending,
-        separated_pair(device, tag(": "), separated_list1(space1, device)),
-    )
-    .map(|vec| vec.into_iter().collect())
-    .parse(input)
+    #[tracing::instrument(skip(input), err)]
+    fn solve(&self, input: &Self::Input) -> miette::Result<Self::Output> {
+        let connections = input;
+        let paths = self.route(connections)?.count()?;
+        Ok(paths)
+    }
 }
 
-fn device(input: &str) -> IResult<&str, Device> {
-    alpha1
-        .map(|device| match device {
-            "you" => Device::You,
-            "out" => Device::Out,
-            other => Device::Other(Box::from(other)),
-        })
-        .parse(input)
+impl Part1 {
+    /// Paths from the start to the end, to inspect rather than count them.
+    pub fn route<'a>(&self, connections: &'a Graph) -> miette::Result<Route<'a>> {
+        Route::new(connections, START, END)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use advent::{Answer, Example, graph};
+
     use super::*;
 
     #[test_log::test]
     fn example_works() -> miette::Result<()> {
-        let input = "\
-aaa: you hhh
-you: bbb ccc
-bbb: ddd eee
-ccc: ddd eee fff
-ddd: ggg
-eee: out
-fff: out
-ggg: out
-hhh: ccc fff iii
-iii: out\
-";
-        let expected = 5;
-        let solution = solve(input)?;
-        assert_eq!(solution, expected);
+        let example = Example::load(env!("CARGO_MANIFEST_DIR"), 1)?;
+        let solution = Part1.run(&example.input)?;
+        assert_eq!(Answer::from(solution), example.answer);
+        Ok(())
+    }
+
+    #[test_log::test]
+    fn paths_can_be_inspected() -> miette::Result<()> {
+        let example = Example::load(env!("CARGO_MANIFEST_DIR"), 1)?;
+        let connections = Part1.parse(&example.input, Mode::Strict)?;
+        let route = Part1.route(&connections)?;
+        let paths = route.paths().collect::<Vec<_>>();
+        assert_eq!(paths.len(), 5);
+        assert_eq!(paths[0], ["you", "bbb", "ddd", "ggg", "out"]);
+        let shortest = route.shortest_paths().map(|path| path.len());
+        assert_eq!(shortest.collect::<Vec<_>>(), [4, 4, 4, 5, 5]);
+        let dot = route.dot();
+        assert!(dot.contains("\"you\" [shape=doublecircle];"));
+        assert!(dot.contains("\"ccc\" -> \"fff\";"));
         Ok(())
     }
+
+    #[test_log::test]
+    fn finds_mandatory_devices() -> miette::Result<()> {
+        let example = Example::load(env!("CARGO_MANIFEST_DIR"), 1)?;
+        let connections = Part1.parse(&example.input, Mode::Strict)?;
+        let route = Part1.route(&connections)?;
+        assert_eq!(route.mandatory_devices()?, ["you", "out"]);
+        assert!(route.articulation_devices()?.is_empty());
+        let connections = Part1.parse("you: aaa\naaa: out\nbbb: you\n", Mode::Strict)?;
+        assert_eq!(Part1.route(&connections)?.articulation_devices()?, ["aaa"]);
+        Ok(())
+    }
+
+    /// Connections from the start through the layers of [`graph::layered`] to the
+    /// end, so that the number of paths doubles with every layer.
+    fn layered(layers: usize) -> String {
+        let mut connections = format!("you: {}\n", graph::layer(0).join(" "));
+        for (device, outputs) in graph::layered(layers) {
+            connections += &format!("{device}: {}\n", outputs.join(" "));
+        }
+        for device in graph::layer(layers) {
+            connections += &format!("{device}: out\n");
+        }
+        connections
+    }
+
+    #[test_log::test]
+    fn counts_paths_beyond_u64() -> miette::Result<()> {
+        assert_eq!(Part1.run(&layered(100))?, 1 << 101);
+        let err = Part1.run(&layered(130)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "more paths pass through xbci than a u128 can count"
+        );
+        Ok(())
+    }
+
+    #[test_log::test]
+    fn reports_invalid_connections() {
+        let err = Part1.run("you: aaa\naaa: bbb out\nbbb: you\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "devices are connected in a cycle: you -> aaa -> bbb -> you"
+        );
+        let err = Part1.run("you: aaa bbb\naaa: out\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "devices bbb are used as outputs but never defined"
+        );
+    }
 }
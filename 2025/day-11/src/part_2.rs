@@ -1,150 +1,150 @@
-use std::{collections::HashMap, fmt};
-
-use miette::{Context, miette};
-use nom::{
-    IResult, Parser,
-    bytes::complete::tag,
-    character::complete::{alpha1, line_ending, space1},
-    multi::separated_list1,
-    sequence::separated_pair,
+use std::borrow::Cow;
+
+use advent::{
+    Solution,
+    graph::Graph,
+    parse::{Mode, parse_all},
 };
 
-#[tracing::instrument(skip(input), err)]
-pub fn solve(input: &str) -> miette::Result<u64> {
-    let (_, connections) = connections
-        .parse(input)
-        .map_err(|err| miette!("{err}"))
-        .context("parse connections")?;
-    let paths = number_of_paths(
-        &connections,
-        &Device::Srv,
-        &[Device::Fft, Device::Dac],
-        &Device::Out,
-    );
-    Ok(paths)
-}
+use crate::devices::{Count, END, Route, connections, validate};
 
-#[derive(Clone, Hash, PartialEq, Eq)]
-enum Device {
-    Srv,
-    Out,
-    Fft,
-    Dac,
-    Other(Box<str>),
-}
+/// Devices every path has to pass through on the real puzzle input.
+pub const CHECKPOINTS: &[Cow<'static, str>] = &[Cow::Borrowed("fft"), Cow::Borrowed("dac")];
+
+/// This part on the puzzle's own checkpoints, to be borrowed for as long as
+/// the program runs.
+pub static PUZZLE: Part2 = Part2::new();
+
+const START: &str = "svr";
 
-fn number_of_paths(
-    connections: &HashMap<Device, Vec<Device>>,
-    start: &Device,
-    must_pass: &[Device],
-    end: &Device,
-) -> u64 {
-    let mut dp = HashMap::new();
-    number_of_paths_dp(&mut dp, connections, start, must_pass, end)
+/// Counts the paths that pass every one of `checkpoints`, the puzzle's own
+/// unless others are given at runtime.
+#[derive(Clone, Debug)]
+pub struct Part2 {
+    pub checkpoints: Cow<'static, [Cow<'static, str>]>,
 }
 
-fn number_of_paths_dp(
-    dp: &mut HashMap<(Device, String), u64>,
-    connections: &HashMap<Device, Vec<Device>>,
-    start: &Device,
-    must_pass: &[Device],
-    end: &Device,
-) -> u64 {
-    let pass_key = must_pass
-        .iter()
-        .map(|node| format!("{node:?}"))
-        .collect::<String>();
-    let cache_key = (start.clone(), pass_key);
-    if let Some(cached) = dp.get(&cache_key).copied() {
-        return cached;
+impl Part2 {
+    pub const fn new() -> Self {
+        Self {
+            checkpoints: Cow::Borrowed(CHECKPOINTS),
+        }
+    }
+
+    pub fn with_checkpoints(checkpoints: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            checkpoints: checkpoints.into_iter().map(Cow::Owned).collect(),
+        }
     }
-    let computed = if start == end && must_pass.is_empty() {
-        1
-    } else {
-        let modified_must_pass = if must_pass.contains(start) {
-            Some(
-                must_pass
-                    .iter()
-                    .filter(|&node| node != start)
-                    .cloned()
-                    .collect::<Vec<_>>(),
-            )
-        } else {
-            None
-        };
-        let must_pass = modified_must_pass.as_deref().unwrap_or(must_pass);
-        connections
-            .get(start)
-            .map(|outputs| {
-                outputs
-                    .iter()
-                    .map(|output| number_of_paths_dp(dp, connections, output, must_pass, end))
-                    .sum()
-            })
-            .unwrap_or(0)
-    };
-    dp.insert(cache_key, computed);
-    computed
 }
 
-fn connections(input: &str) -> IResult<&str, HashMap<Device, Vec<Device>>> {
-    separated_list1(
-        line_ending,
-        separated_pair(device, tag(": "), separated_list1(space1, device)),
-    )
-    .map(|vec| vec.into_iter().collect())
-    .parse(input)
+impl Default for Part2 {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-fn device(input: &str) -> IResult<&str, Device> {
-    alpha1
-        .map(|device| match device {
-            "svr" => Device::Srv,
-            "out" => Device::Out,
-            "fft" => Device::Fft,
-            "dac" => Device::Dac,
-            other => Device::Other(Box::from(other)),
-        })
-        .parse(input)
+impl Solution for Part2 {
+    type Input = Graph;
+    type Output = Count;
+
+    #[tracing::instrument(skip(input), err)]
+    fn parse(&self, input: &str, mode: Mode) -> miette::Result<Self::Input> {
+        let connections = parse_all(connections, input, mode, "parse connections")?;
+        validate(&connections)?;
+        Ok(connections)
+    }
+
+    #[tracing::instrument(skip(input), err)]
+    fn solve(&self, input: &Self::Input) -> miette::Result<Self::Output> {
+        let connections = input;
+        let paths = self.route(connections)?.count()?;
+        Ok(paths)
+    }
 }
 
-impl fmt::Debug for Device {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::Srv => "svr",
-            Self::Out => "out",
-            Self::Fft => "fft",
-            Self::Dac => "dac",
-            Self::Other(other) => other,
-        }
-        .fmt(f)
+impl Part2 {
+    /// Paths from the start to the end through every checkpoint, to inspect
+    /// rather than count them.
+    pub fn route<'a>(&self, connections: &'a Graph) -> miette::Result<Route<'a>> {
+        Route::new(connections, START, END)?.through(&self.checkpoints)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use advent::{Answer, Example};
+
     use super::*;
 
     #[test_log::test]
     fn example_works() -> miette::Result<()> {
-        let input = "\
-svr: aaa bbb
-aaa: fft
-fft: ccc
-bbb: tty
-tty: ccc
-ccc: ddd eee
-ddd: hub
-hub: fff
-eee: dac
-dac: fff
-fff: ggg hhh
-ggg: out
-hhh: out\
-";
-        let expected = 2;
-        let solution = solve(input)?;
-        assert_eq!(solution, expected);
+        let example = Example::load(env!("CARGO_MANIFEST_DIR"), 2)?;
+        let solution = Part2::default().run(&example.input)?;
+        assert_eq!(Answer::from(solution), example.answer);
+        Ok(())
+    }
+
+    #[test_log::test]
+    fn paths_can_be_inspected() -> miette::Result<()> {
+        let example = Example::load(env!("CARGO_MANIFEST_DIR"), 2)?;
+        let part = Part2::default();
+        let connections = part.parse(&example.input, Mode::Strict)?;
+        let route = part.route(&connections)?;
+        let paths = route.paths().collect::<Vec<_>>();
+        assert_eq!(paths.len(), 2);
+        assert!(paths.iter().all(|path| path.contains(&"fft")));
+        assert_eq!(route.shortest_paths().count(), 2);
+        let dot = route.dot();
+        assert!(dot.contains("\"svr\" [shape=doublecircle];"));
+        assert!(dot.contains("\"dac\" [style=filled, fillcolor=gold];"));
+        Ok(())
+    }
+
+    #[test_log::test]
+    fn finds_mandatory_devices() -> miette::Result<()> {
+        let example = Example::load(env!("CARGO_MANIFEST_DIR"), 2)?;
+        let part = Part2::default();
+        let connections = part.parse(&example.input, Mode::Strict)?;
+        let route = part.route(&connections)?;
+        assert_eq!(route.mandatory_devices()?, ["svr", "ccc", "fff", "out"]);
+        assert_eq!(route.articulation_devices()?, ["ccc", "fff"]);
+        Ok(())
+    }
+
+    #[test_log::test]
+    fn any_checkpoints_work() -> miette::Result<()> {
+        let example = Example::load(env!("CARGO_MANIFEST_DIR"), 2)?;
+        let paths = |checkpoints: &[&str]| {
+            Part2::with_checkpoints(checkpoints.iter().map(|&name| name.to_owned()))
+                .run(&example.input)
+        };
+        assert_eq!(paths(&[])?, 8);
+        assert_eq!(paths(&["fft"])?, 4);
+        assert_eq!(paths(&["hub", "fft", "ggg"])?, 1);
+        assert_eq!(paths(&["dac", "hub"])?, 0);
+        Ok(())
+    }
+
+    #[test_log::test]
+    fn passes_more_checkpoints_than_bits_in_a_word() -> miette::Result<()> {
+        let names = (0..100)
+            .map(|n| {
+                format!(
+                    "d{}{}",
+                    char::from(b'a' + n / 26),
+                    char::from(b'a' + n % 26)
+                )
+            })
+            .collect::<Vec<_>>();
+        let mut connections = format!("svr: {} out\n", names[0]);
+        for pair in names.windows(2) {
+            connections += &format!("{}: {}\n", pair[0], pair[1]);
+        }
+        connections += &format!("{}: out\n", names[99]);
+        assert_eq!(Part2::with_checkpoints(names.clone()).run(&connections)?, 1);
+        let with_start = names.into_iter().chain(["svr".to_owned()]).rev();
+        assert_eq!(Part2::with_checkpoints(with_start).run(&connections)?, 1);
         Ok(())
     }
 }
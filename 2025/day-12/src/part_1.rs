@@ -1,39 +1,257 @@
-use miette::{Context, miette};
+use advent::{
+    Solution,
+    grid::{Cell, Grid, Position, grid},
+    parse::{Mode, parse_all},
+    polyomino::Polyomino,
+};
+use miette::bail;
 use nom::{
     IResult, Parser,
-    branch::alt,
     bytes::complete::tag,
     character::complete::{self, digit1, line_ending, space1},
-    multi::{many1, separated_list1},
+    multi::separated_list1,
     sequence::{preceded, separated_pair, terminated},
 };
 
-#[tracing::instrument(skip(input), err)]
-pub fn solve(input: &str) -> miette::Result<u64> {
-    let (_, (_, regions)) = puzzle
-        .parse(input)
-        .map_err(|err| miette!("{err}"))
-        .context("parse puzzle")?;
-    let fitting_regions = regions
-        .into_iter()
-        .map(|region| {
-            (
-                region.width * region.length,
-                region.quantities.iter().sum::<u64>(),
-            )
-        })
-        .filter(|&(area, shapes)| area >= shapes * 9)
-        .count() as u64;
-    Ok(fitting_regions)
-}
-
-type Shape = Vec<Vec<Option<ShapePart>>>;
-
-#[derive(Clone, Copy, Debug)]
-struct ShapePart;
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Part1;
+
+impl Solution for Part1 {
+    type Input = (Vec<Shape>, Vec<Region>);
+    type Output = u64;
+
+    #[tracing::instrument(skip(input), err)]
+    fn parse(&self, input: &str, mode: Mode) -> miette::Result<Self::Input> {
+        let puzzle = parse_all(puzzle, input, mode, "parse puzzle")?;
+        Ok(puzzle)
+    }
+
+    #[tracing::instrument(skip(input), err)]
+    fn solve(&self, input: &Self::Input) -> miette::Result<Self::Output> {
+        let (shapes, regions) = input;
+        if let Some(region) = regions
+            .iter()
+            .find(|region| region.quantities.len() > shapes.len())
+        {
+            bail!(
+                "region {}x{} asks for {} shapes but only {} are given",
+                region.width,
+                region.length,
+                region.quantities.len(),
+                shapes.len()
+            );
+        }
+        let pieces = shapes.iter().map(Piece::new).collect::<Vec<_>>();
+        let fitting_regions = regions
+            .iter()
+            .filter(|region| fits(&pieces, region))
+            .count() as u64;
+        Ok(fitting_regions)
+    }
+}
+
+/// Whether the shapes a region asks for can all be placed in it without overlapping.
+///
+/// Cheap checks settle most regions: too little area, enough room to give every
+/// shape a box of its own, or no way to split the cells the shapes cover between
+/// the colours of a checkerboard. Only the rest are searched for a packing.
+/// Shapes without cells take no room, so there is nothing to place for them.
+fn fits(pieces: &[Piece], region: &Region) -> bool {
+    let area = region.width * region.length;
+    let quantities = pieces
+        .iter()
+        .zip(&region.quantities)
+        .map(|(piece, &quantity)| if piece.cells == 0 { 0 } else { quantity })
+        .collect::<Vec<_>>();
+    let placed = || pieces.iter().zip(quantities.iter().copied());
+    let cells = placed()
+        .map(|(piece, quantity)| piece.cells * quantity)
+        .sum::<u64>();
+    if cells > area {
+        return false;
+    }
+
+    let count = quantities.iter().sum::<u64>();
+    let (box_width, box_height) = placed()
+        .filter(|&(_, quantity)| quantity > 0)
+        .fold((1, 1), |(width, height), (piece, _)| {
+            (width.max(piece.width), height.max(piece.height))
+        });
+    let boxes = |(width, height): (u64, u64)| (region.width / width) * (region.length / height);
+    if boxes((box_width, box_height)).max(boxes((box_height, box_width))) >= count {
+        return true;
+    }
+
+    let dark = area / 2;
+    let light = area - dark;
+    let covered_dark = dark_cells_covered(placed());
+    if !(cells.saturating_sub(light)..=dark).any(|covered| covered_dark[covered as usize]) {
+        return false;
+    }
+
+    let (width, length) = (region.width as usize, region.length as usize);
+    let mut packing = Packing {
+        pieces,
+        remaining: quantities,
+        board: Grid::filled(width.min(length), width.max(length), false),
+        slack: area - cells,
+    };
+    packing.pack(0)
+}
+
+/// Which numbers of dark checkerboard cells the pieces can cover together,
+/// indexed by that number, as each covers its fewest of either colour or its most.
+fn dark_cells_covered<'a>(pieces: impl Iterator<Item = (&'a Piece, u64)>) -> Vec<bool> {
+    let mut covered = vec![true];
+    for (piece, quantity) in pieces {
+        let (fewest, most) = (
+            piece.fewest_of_a_colour,
+            piece.cells - piece.fewest_of_a_colour,
+        );
+        for _ in 0..quantity {
+            let mut next = vec![false; covered.len() + most as usize];
+            for (dark, _) in covered.iter().enumerate().filter(|&(_, &can)| can) {
+                next[dark + fewest as usize] = true;
+                next[dark + most as usize] = true;
+            }
+            covered = next;
+        }
+    }
+    covered
+}
+
+/// Row and column of a cell relative to another.
+type Offset = (isize, isize);
+
+/// A shape in each of its distinct rotations and reflections.
+#[derive(Clone, Debug)]
+struct Piece {
+    cells: u64,
+    width: u64,
+    height: u64,
+    /// Least cells the shape covers of either colour of a checkerboard.
+    fewest_of_a_colour: u64,
+    /// Offsets of the cells of each orientation from its first cell in reading order.
+    orientations: Vec<Vec<Offset>>,
+}
+
+impl Piece {
+    fn new(shape: &Shape) -> Self {
+        let dark = shape
+            .cells()
+            .iter()
+            .filter(|&&(row, column)| (row + column) % 2 == 0)
+            .count();
+        let orientations = shape
+            .variants()
+            .iter()
+            .map(|variant| {
+                let cells = variant.cells();
+                let (first_row, first_column) = cells.first().copied().unwrap_or_default();
+                cells
+                    .iter()
+                    .map(|&(row, column)| {
+                        (
+                            row as isize - first_row as isize,
+                            column as isize - first_column as isize,
+                        )
+                    })
+                    .collect()
+            })
+            .collect();
+        Self {
+            cells: shape.len() as u64,
+            width: shape.width() as u64,
+            height: shape.height() as u64,
+            fewest_of_a_colour: dark.min(shape.len() - dark) as u64,
+            orientations,
+        }
+    }
+}
+
+/// Backtracking search filling the board in reading order.
+///
+/// The board is laid out with its short side as the rows, so that fewer cells
+/// are left half decided at any time and dead ends show sooner.
+///
+/// The first free cell is either covered by the first cell of some orientation
+/// of a piece still to place, or left empty while there is area to spare.
+struct Packing<'a> {
+    pieces: &'a [Piece],
+    remaining: Vec<u64>,
+    board: Grid<bool>,
+    slack: u64,
+}
+
+impl Packing<'_> {
+    fn pack(&mut self, from: usize) -> bool {
+        if self.remaining.iter().all(|&quantity| quantity == 0) {
+            return true;
+        }
+        let width = self.board.width();
+        let Some(first_free) =
+            (from..width * self.board.height()).find(|&i| !self.board[(i / width, i % width)])
+        else {
+            return false;
+        };
+        let position = (first_free / width, first_free % width);
+        for piece in 0..self.pieces.len() {
+            if self.remaining[piece] == 0 {
+                continue;
+            }
+            for orientation in &self.pieces[piece].orientations {
+                let Some(covered) = self.cover(position, orientation) else {
+                    continue;
+                };
+                covered.iter().for_each(|&cell| self.board[cell] = true);
+                self.remaining[piece] -= 1;
+                if self.pack(first_free + 1) {
+                    return true;
+                }
+                self.remaining[piece] += 1;
+                covered.iter().for_each(|&cell| self.board[cell] = false);
+            }
+        }
+        if self.slack > 0 {
+            self.slack -= 1;
+            self.board[position] = true;
+            if self.pack(first_free + 1) {
+                return true;
+            }
+            self.board[position] = false;
+            self.slack += 1;
+        }
+        false
+    }
+
+    /// Cells the orientation covers with its first cell at `position`, if they are all free.
+    fn cover(&self, (row, column): Position, orientation: &[Offset]) -> Option<Vec<Position>> {
+        orientation
+            .iter()
+            .map(|&(row_offset, column_offset)| {
+                let cell = (
+                    row.checked_add_signed(row_offset)?,
+                    column.checked_add_signed(column_offset)?,
+                );
+                self.board
+                    .get(cell)
+                    .is_some_and(|&taken| !taken)
+                    .then_some(cell)
+            })
+            .collect()
+    }
+}
+
+type Shape = Polyomino;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShapePart {
+    Filled,
+    Empty,
+}
 
 #[derive(Clone, Debug)]
-struct Region {
+pub struct Region {
     width: u64,
     length: u64,
     quantities: Vec<u64>,
@@ -62,7 +280,9 @@ fn regions(input: &str) -> IResult<&str, Vec<Region>> {
 }
 
 fn shape(input: &str) -> IResult<&str, Shape> {
-    separated_list1(line_ending, many1(shape_part)).parse(input)
+    grid::<ShapePart>
+        .map(|shape| Polyomino::from_grid(&shape, |&part| part == ShapePart::Filled))
+        .parse(input)
 }
 
 fn region(input: &str) -> IResult<&str, Region> {
@@ -79,59 +299,85 @@ fn region(input: &str) -> IResult<&str, Region> {
     .parse(input)
 }
 
-fn shape_part(input: &str) -> IResult<&str, Option<ShapePart>> {
-    alt((tag("#").map(|_| Some(ShapePart)), tag(".").map(|_| None))).parse(input)
-}
-
 fn dimensions(input: &str) -> IResult<&str, (u64, u64)> {
     separated_pair(complete::u64, tag("x"), complete::u64).parse(input)
 }
 
+impl Cell for ShapePart {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '#' => Some(Self::Filled),
+            '.' => Some(Self::Empty),
+            _ => None,
+        }
+    }
+
+    fn to_char(&self) -> char {
+        match self {
+            Self::Filled => '#',
+            Self::Empty => '.',
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use advent::{Answer, Example};
+
     use super::*;
 
-    #[allow(unused_variables)]
     #[test_log::test]
     fn example_works() -> miette::Result<()> {
-        let input = "\
-0:
-###
-##.
-##.
-
-1:
-###
-##.
-.##
-
-2:
-.##
-###
-##.
-
-3:
-##.
-###
-##.
-
-4:
-###
-#..
-###
-
-5:
-###
-.#.
-###
-
-4x4: 0 0 0 0 2 0
-12x5: 1 0 1 0 2 2
-12x5: 1 0 1 0 3 2
-";
-        let expected = 2;
-        let solution = solve(input)?;
-        // assert_eq!(solution, expected);
+        let example = Example::load(env!("CARGO_MANIFEST_DIR"), 1)?;
+        let solution = Part1.run(&example.input)?;
+        assert_eq!(Answer::from(solution), example.answer);
         Ok(())
     }
+
+    fn pieces(drawings: &[&str]) -> Vec<Piece> {
+        drawings
+            .iter()
+            .map(|drawing| Piece::new(&shape(drawing).unwrap().1))
+            .collect()
+    }
+
+    fn region(width: u64, length: u64, quantities: &[u64]) -> Region {
+        Region {
+            width,
+            length,
+            quantities: quantities.to_vec(),
+        }
+    }
+
+    #[test_log::test]
+    fn packs_mirrored_pieces() {
+        // Three of these only fit in four by four with one of them mirrored.
+        let pieces = pieces(&["##\n##\n#."]);
+        assert!(fits(&pieces, &region(4, 4, &[3])));
+    }
+
+    #[test_log::test]
+    fn rejects_by_checkerboard_colours() {
+        // Every T covers an odd number of dark cells, so five cannot cover ten.
+        let pieces = pieces(&["###\n.#."]);
+        assert!(!fits(&pieces, &region(4, 5, &[5])));
+        assert!(!dark_cells_covered(pieces.iter().zip([5]))[10]);
+        assert!(fits(&pieces, &region(4, 4, &[4])));
+    }
+
+    #[test_log::test]
+    fn rejects_by_searching() {
+        // Whichever way the S is turned, the two cells it leaves are not adjacent.
+        let pieces = pieces(&["##.\n.##", "##"]);
+        assert!(!fits(&pieces, &region(3, 2, &[1, 1])));
+        assert!(dark_cells_covered(pieces.iter().zip([1, 1]))[3]);
+    }
+
+    #[test_log::test]
+    fn ignores_empty_shapes() {
+        let pieces = pieces(&["##", "..\n.."]);
+        assert!(fits(&pieces, &region(2, 1, &[1, 1])));
+        assert!(fits(&pieces, &region(1, 1, &[0, 3])));
+        assert!(!fits(&pieces, &region(1, 1, &[1, 0])));
+    }
 }
@@ -1,5 +1,20 @@
+use advent::{Solution, parse::Mode};
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Part2;
+
 #[allow(unused_variables)]
-#[tracing::instrument(skip(input), err)]
-pub fn solve(input: &str) -> miette::Result<&'static str> {
-    Ok("Merry Christmas")
+impl Solution for Part2 {
+    type Input = ();
+    type Output = &'static str;
+
+    #[tracing::instrument(skip(input), err)]
+    fn parse(&self, input: &str, mode: Mode) -> miette::Result<Self::Input> {
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(input), err)]
+    fn solve(&self, input: &Self::Input) -> miette::Result<Self::Output> {
+        Ok("Merry Christmas")
+    }
 }